@@ -1,10 +1,14 @@
-use crate::{CpuidResult};
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 
 /// Physical/Virtual Addresses size (bit) available from `CPUID.(EAX=8000_0008h):EAX`
 #[derive(Debug, Clone)]
 pub struct AddressSize {
+    /// EAX[7:0]
     pub physical: u8,
+    /// EAX[15:8]
     pub virtual_: u8,
+    /// EAX[23:16]: Maximum guest physical address size (AMD), 0 indicates that it is the same as `physical`
+    pub guest_physical: u8,
 }
 
 impl From<&CpuidResult> for AddressSize {
@@ -12,12 +16,38 @@ impl From<&CpuidResult> for AddressSize {
         Self {
             physical: (cpuid.eax & 0xFF) as u8,
             virtual_: ((cpuid.eax >> 8) & 0xFF) as u8,
+            guest_physical: ((cpuid.eax >> 16) & 0xFF) as u8,
         }
     }
 }
 
 impl AddressSize {
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x8000_0008, 0x0))
+        Self::get_from_source(&LiveCpuid)
     }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x8000_0008, 0x0))
+    }
+
+    /// Maximum guest physical address size for nested paging
+    pub fn guest_physical_size(&self) -> u8 {
+        if self.guest_physical != 0 {
+            self.guest_physical
+        } else {
+            self.physical
+        }
+    }
+}
+
+#[test]
+fn test_address_size() {
+    let addr_size = AddressSize::from(&CpuidResult { eax: 0x0030_3034, ebx: 0x0, ecx: 0x0, edx: 0x0 });
+
+    assert_eq!((addr_size.physical, addr_size.virtual_), (52, 48));
+    assert_eq!(addr_size.guest_physical_size(), 48);
+
+    let addr_size = AddressSize::from(&CpuidResult { eax: 0x0000_3030, ebx: 0x0, ecx: 0x0, edx: 0x0 });
+
+    assert_eq!(addr_size.guest_physical_size(), 48);
 }
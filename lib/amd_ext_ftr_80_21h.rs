@@ -0,0 +1,83 @@
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
+
+/// Extended Feature Identification 2, `CPUID.(EAX=8000_0021h)`, AMD CPU only.
+/// ECX and EDX are reserved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmdExtFeature2 {
+    /// EAX: Feature flags (NoNestedDataBp, AutomaticIBRS, ERAPS, SBPB, ...)
+    pub features: u32,
+    /// EBX[11:0]: Size of the microcode patch in 16-byte multiples
+    pub microcode_patch_size: u16,
+    /// EBX[23:16]: Return Address Predictor size
+    pub rap_size: u8,
+}
+
+impl From<&CpuidResult> for AmdExtFeature2 {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            features: cpuid.eax,
+            microcode_patch_size: (cpuid.ebx & 0xFFF) as u16,
+            rap_size: ((cpuid.ebx >> 16) & 0xFF) as u8,
+        }
+    }
+}
+
+impl AmdExtFeature2 {
+    /// If the microcode patch size is 0, the patch is at most 5568 (15C0h) bytes in size
+    pub const DEFAULT_MAX_MICROCODE_PATCH_BYTES: u32 = 0x15C0;
+
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if the leaf is not supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if src.cpuid(0x8000_0000, 0x0).eax < 0x8000_0021 {
+            return None;
+        }
+
+        Some(Self::from(&src.cpuid(0x8000_0021, 0x0)))
+    }
+
+    /// Maximum size of the microcode patch in bytes
+    pub fn microcode_patch_bytes(&self) -> u32 {
+        match self.microcode_patch_size {
+            0 => Self::DEFAULT_MAX_MICROCODE_PATCH_BYTES,
+            size => size as u32 * 16,
+        }
+    }
+
+    /// Minimum number of CALL instructions software needs to execute to flush the RAP,
+    /// `None` if the RAP size is not reported
+    pub fn rap_flush_calls(&self) -> Option<u32> {
+        if self.rap_size != 0 {
+            Some(self.rap_size as u32 * 8)
+        } else {
+            None
+        }
+    }
+
+    /// EAX[8]: Automatic IBRS
+    pub fn automatic_ibrs(&self) -> bool {
+        ((self.features >> 8) & 0b1) != 0
+    }
+
+    /// EAX[24]: Enhanced Return Address Predictor Security
+    pub fn eraps(&self) -> bool {
+        ((self.features >> 24) & 0b1) != 0
+    }
+}
+
+#[test]
+fn test_amd_ext_feature2() {
+    /* Zen 5 */
+    let ext_ftr = AmdExtFeature2::from(&CpuidResult { eax: 0x0146_AFCF, ebx: 0x0004_0000, ecx: 0x0, edx: 0x0 });
+
+    assert!(ext_ftr.automatic_ibrs() && ext_ftr.eraps());
+    assert_eq!(ext_ftr.microcode_patch_bytes(), AmdExtFeature2::DEFAULT_MAX_MICROCODE_PATCH_BYTES);
+    assert_eq!((ext_ftr.rap_size, ext_ftr.rap_flush_calls()), (4, Some(32)));
+
+    let ext_ftr = AmdExtFeature2::from(&CpuidResult { eax: 0x0, ebx: 0x0000_0200, ecx: 0x0, edx: 0x0 });
+
+    assert_eq!((ext_ftr.microcode_patch_bytes(), ext_ftr.rap_flush_calls()), (8192, None));
+}
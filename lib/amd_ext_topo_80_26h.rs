@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult};
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 #[cfg(feature = "std")]
 use std::fmt;
 
@@ -166,12 +166,20 @@ impl From<&CpuidResult> for AmdExtTopo {
 
 impl AmdExtTopo {
     pub fn get(sub_leaf: u32) -> Self {
-        Self::from(&cpuid!(LEAF, sub_leaf))
+        Self::get_from_source(&LiveCpuid, sub_leaf)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S, sub_leaf: u32) -> Self {
+        Self::from(&src.cpuid(LEAF, sub_leaf))
     }
 
     pub fn is_supported() -> bool {
+        Self::is_supported_from_source(&LiveCpuid)
+    }
+
+    pub fn is_supported_from_source<S: CpuidSource>(src: &S) -> bool {
         const INPUT_ECX: u32 = 0x1;
-        let cpuid = cpuid!(LEAF, INPUT_ECX);
+        let cpuid = src.cpuid(LEAF, INPUT_ECX);
 
         (cpuid.ecx & 0xFF) == INPUT_ECX
     }
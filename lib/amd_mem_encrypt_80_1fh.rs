@@ -0,0 +1,93 @@
+use crate::{CpuidResult, CpuidSource, CpuVendor, LiveCpuid};
+
+/// AMD Secure Memory Encryption and Secure Encrypted Virtualization, `CPUID.(EAX=8000_001Fh)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmdMemEncrypt {
+    /// EAX: Feature flags (SME, SEV, SEV-ES, SEV-SNP, ...)
+    pub features: u32,
+    /// EBX[5:0]: Page table bit number used to enable memory encryption
+    pub c_bit: u8,
+    /// EBX[11:6]: Reduction of the physical address space in bits when memory encryption is enabled
+    pub phys_addr_reduction: u8,
+    /// EBX[15:12]: Number of VM Permission Levels supported
+    pub num_vmpl: u8,
+    /// ECX: Number of encrypted guests supported simultaneously
+    pub num_encrypted_guests: u32,
+    /// EDX: Minimum ASID value for an SEV enabled, SEV-ES disabled guest
+    pub min_sev_no_es_asid: u32,
+}
+
+impl From<&CpuidResult> for AmdMemEncrypt {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            features: cpuid.eax,
+            c_bit: (cpuid.ebx & 0x3F) as u8,
+            phys_addr_reduction: ((cpuid.ebx >> 6) & 0x3F) as u8,
+            num_vmpl: ((cpuid.ebx >> 12) & 0xF) as u8,
+            num_encrypted_guests: cpuid.ecx,
+            min_sev_no_es_asid: cpuid.edx,
+        }
+    }
+}
+
+impl AmdMemEncrypt {
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if the CPU is not AMD or the leaf is not supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if CpuVendor::get_from_source(src) != CpuVendor::AuthenticAMD
+        || src.cpuid(0x8000_0000, 0x0).eax < 0x8000_001F {
+            return None;
+        }
+
+        Some(Self::from(&src.cpuid(0x8000_001F, 0x0)))
+    }
+
+    fn has(&self, bit: u32) -> bool {
+        ((self.features >> bit) & 0b1) != 0
+    }
+
+    /// EAX[0]: Secure Memory Encryption
+    pub fn sme(&self) -> bool {
+        self.has(0)
+    }
+
+    /// EAX[1]: Secure Encrypted Virtualization
+    pub fn sev(&self) -> bool {
+        self.has(1)
+    }
+
+    /// EAX[3]: SEV Encrypted State
+    pub fn sev_es(&self) -> bool {
+        self.has(3)
+    }
+
+    /// EAX[4]: SEV Secure Nested Paging
+    pub fn sev_snp(&self) -> bool {
+        self.has(4)
+    }
+
+    /// EAX[5]: VM Permission Levels
+    pub fn vmpl(&self) -> bool {
+        self.has(5)
+    }
+
+    /// ASIDs of the SEV-ES guests are `1..min_sev_no_es_asid`,
+    /// and the SEV guests without SEV-ES are `min_sev_no_es_asid..=num_encrypted_guests`
+    pub fn sev_es_asid_range(&self) -> core::ops::Range<u32> {
+        1..self.min_sev_no_es_asid.max(1)
+    }
+}
+
+#[test]
+fn test_amd_mem_encrypt() {
+    let cpuid = CpuidResult { eax: 0x0101FD3F, ebx: 0x00004173, ecx: 0x000003EE, edx: 0x00000065 };
+    let mem_encrypt = AmdMemEncrypt::from(&cpuid);
+
+    assert!(mem_encrypt.sme() && mem_encrypt.sev() && mem_encrypt.sev_es() && mem_encrypt.sev_snp());
+    assert_eq!((mem_encrypt.c_bit, mem_encrypt.phys_addr_reduction, mem_encrypt.num_vmpl), (51, 5, 4));
+    assert_eq!((mem_encrypt.num_encrypted_guests, mem_encrypt.min_sev_no_es_asid), (1006, 101));
+    assert_eq!(mem_encrypt.sev_es_asid_range(), 1..101);
+}
@@ -0,0 +1,79 @@
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
+
+/// Extended Performance Monitoring and Debug, `CPUID.(EAX=8000_0022h)`, AMD CPU only
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmdPerfMon {
+    /// EAX[0]: Performance Monitoring Version 2 supported
+    pub perfmon_v2: bool,
+    /// EAX[1]: Last Branch Record Stack supported
+    pub lbr_stack: bool,
+    /// EAX[2]: Freezing Core Performance Counters and LBR Stack on Core Performance Counter overflow supported
+    pub lbr_and_pmc_freeze: bool,
+    /// EBX[3:0]: Number of Core Performance Counters
+    pub num_core_pmc: u8,
+    /// EBX[9:4]: Number of available Last Branch Record Stack entries
+    pub lbr_stack_size: u8,
+    /// EBX[15:10]: Number of Data Fabric (Northbridge) Performance Counters
+    pub num_df_pmc: u8,
+    /// EBX[21:16]: Number of Unified Memory Controller Performance Counters
+    pub num_umc_pmc: u8,
+    /// ECX: Active UMCs bitmap
+    pub active_umc_mask: u32,
+}
+
+impl From<&CpuidResult> for AmdPerfMon {
+    fn from(cpuid: &CpuidResult) -> Self {
+        let perfmon_v2 = (cpuid.eax & 0b1) == 0b1;
+        let lbr_stack = (cpuid.eax & 0b10) == 0b10;
+        let lbr_and_pmc_freeze = (cpuid.eax & 0b100) == 0b100;
+        let num_core_pmc = (cpuid.ebx & 0xF) as u8;
+        let lbr_stack_size = ((cpuid.ebx >> 4) & 0x3F) as u8;
+        let num_df_pmc = ((cpuid.ebx >> 10) & 0x3F) as u8;
+        let num_umc_pmc = ((cpuid.ebx >> 16) & 0x3F) as u8;
+        let active_umc_mask = cpuid.ecx;
+
+        Self {
+            perfmon_v2,
+            lbr_stack,
+            lbr_and_pmc_freeze,
+            num_core_pmc,
+            lbr_stack_size,
+            num_df_pmc,
+            num_umc_pmc,
+            active_umc_mask,
+        }
+    }
+}
+
+impl AmdPerfMon {
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if the leaf is not supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if src.cpuid(0x8000_0000, 0x0).eax < 0x8000_0022 {
+            return None;
+        }
+
+        Some(Self::from(&src.cpuid(0x8000_0022, 0x0)))
+    }
+
+    /// Number of the active UMCs
+    pub fn num_active_umc(&self) -> u32 {
+        self.active_umc_mask.count_ones()
+    }
+}
+
+#[test]
+fn test_amd_perfmon() {
+    let cpuid = CpuidResult { eax: 0x00000007, ebx: 0x00084106, ecx: 0x00000003, edx: 0x00000000 };
+    let perfmon = AmdPerfMon::from(&cpuid);
+
+    assert!(perfmon.perfmon_v2 && perfmon.lbr_stack && perfmon.lbr_and_pmc_freeze);
+    assert_eq!(
+        [perfmon.num_core_pmc, perfmon.lbr_stack_size, perfmon.num_df_pmc, perfmon.num_umc_pmc],
+        [6, 16, 16, 8],
+    );
+    assert_eq!(perfmon.num_active_umc(), 2);
+}
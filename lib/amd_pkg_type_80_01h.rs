@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult, FamModStep};
+use crate::{CpuidResult, CpuidSource, FamModStep, LiveCpuid};
 
 /* Leaf: 0x8000_0001, AMD CPU only */
 /* ref: https://en.wikipedia.org/wiki/List_of_AMD_CPU_microarchitectures */
@@ -212,6 +212,10 @@ impl From<&CpuidResult> for AmdPkgType {
 
 impl AmdPkgType {
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x8000_0001, 0x0))
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x8000_0001, 0x0))
     }
 }
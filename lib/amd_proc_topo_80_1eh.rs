@@ -1,4 +1,4 @@
-use crate::CpuidResult;
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 
 /// Information available from `CPUID.(EAX=8000_001Eh)`, AMD CPU only
 #[derive(Debug, Clone)]
@@ -30,6 +30,10 @@ impl From<&CpuidResult> for AmdProcTopo {
 
 impl AmdProcTopo {
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x8000_001E, 0x0))
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x8000_001E, 0x0))
     }
 }
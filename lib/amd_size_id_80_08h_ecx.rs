@@ -1,16 +1,20 @@
-use crate::{cpuid, CpuidResult};
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 
-/// Information available from `CPUID.(EAX=8000_0001h)`, AMD CPU only
+/// Information available from `CPUID.(EAX=8000_0008h):ECX, EDX`, AMD CPU only
 #[derive(Debug, Clone)]
 pub struct AmdSizeId {
+    /// ECX[17:16]: Size of the performance time-stamp counter
     pub perf_tsc_size: u8,
+    /// ECX[15:12]: Number of LSBs in the APIC ID that indicate the core ID
     pub apic_id_size: u8,
+    /// ECX[7:0] + 1: Number of threads in the package
     pub num_thread: u8,
+    /// EDX[31:16]: Maximum ECX value recognized by RDPRU
     pub rdpru_max_input: u16,
+    /// EDX[15:0]: Maximum page count for INVLPGB
     pub invlpgb_max_page: u16,
 }
 
-
 impl From<&CpuidResult> for AmdSizeId {
     fn from(cpuid: &CpuidResult) -> Self {
         let perf_tsc_size = 40 + ((cpuid.ecx >> 16) & 0b11) as u8 * 8;
@@ -31,6 +35,10 @@ impl From<&CpuidResult> for AmdSizeId {
 
 impl AmdSizeId {
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x8000_0008, 0x0))
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x8000_0008, 0x0))
     }
 }
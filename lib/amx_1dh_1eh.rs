@@ -0,0 +1,113 @@
+use crate::CpuidResult;
+#[cfg(feature = "std")]
+use crate::{CpuidSource, LiveCpuid};
+
+/// Tile palette `n` of `CPUID.(EAX=1Dh, ECX=n)` (n >= 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmxPalette {
+    pub palette_id: u32,
+    /// EAX[15:0]: Total size of the tile storage in bytes
+    pub total_tile_bytes: u16,
+    /// EAX[31:16]
+    pub bytes_per_tile: u16,
+    /// EBX[15:0]
+    pub bytes_per_row: u16,
+    /// EBX[31:16]: Number of tile registers
+    pub max_names: u16,
+    /// ECX[15:0]
+    pub max_rows: u16,
+}
+
+impl AmxPalette {
+    pub fn from_cpuid(palette_id: u32, cpuid: &CpuidResult) -> Self {
+        Self {
+            palette_id,
+            total_tile_bytes: (cpuid.eax & 0xFFFF) as u16,
+            bytes_per_tile: (cpuid.eax >> 16) as u16,
+            bytes_per_row: (cpuid.ebx & 0xFFFF) as u16,
+            max_names: (cpuid.ebx >> 16) as u16,
+            max_rows: (cpuid.ecx & 0xFFFF) as u16,
+        }
+    }
+}
+
+/// TMUL (Tile Matrix Multiply Unit) information, `CPUID.(EAX=1Eh, ECX=0)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmxTmul {
+    /// EBX[7:0]: Maximum number of rows or columns (K)
+    pub tmul_maxk: u8,
+    /// EBX[23:8]: Maximum number of bytes per column (N)
+    pub tmul_maxn: u16,
+}
+
+impl From<&CpuidResult> for AmxTmul {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            tmul_maxk: (cpuid.ebx & 0xFF) as u8,
+            tmul_maxn: ((cpuid.ebx >> 8) & 0xFFFF) as u16,
+        }
+    }
+}
+
+/// Intel AMX tile palettes and TMUL, `CPUID.(EAX=1Dh)` and `CPUID.(EAX=1Eh)`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmxInfo {
+    /// `CPUID.(EAX=1Dh, ECX=0):EAX`
+    pub max_palette: u32,
+    /// Palette 1 and above, palette 0 is the initialized state
+    pub palettes: Vec<AmxPalette>,
+    /// `None` if the leaf 0x1E is not supported
+    pub tmul: Option<AmxTmul>,
+}
+
+#[cfg(feature = "std")]
+impl AmxInfo {
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if AMX-TILE (`CPUID.(EAX=07h, ECX=0):EDX[24]`) is not supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        let max_leaf = src.cpuid(0x0, 0x0).eax;
+
+        if max_leaf < 0x1D || ((src.cpuid(0x7, 0x0).edx >> 24) & 0b1) == 0 {
+            return None;
+        }
+
+        let max_palette = src.cpuid(0x1D, 0x0).eax;
+        let palettes = (0x1..=max_palette.min(0x40))
+            .map(|palette_id| AmxPalette::from_cpuid(palette_id, &src.cpuid(0x1D, palette_id)))
+            .collect();
+        let tmul = if 0x1E <= max_leaf {
+            Some(AmxTmul::from(&src.cpuid(0x1E, 0x0)))
+        } else {
+            None
+        };
+
+        Some(Self { max_palette, palettes, tmul })
+    }
+}
+
+#[test]
+fn test_amx_info() {
+    use crate::CpuidTable;
+
+    /* Sapphire Rapids */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x20, 0x756E6547, 0x6C65746E, 0x49656E69]),
+        (0x7, 0x0, [0x2, 0x0, 0x0, 0x01C00000]),
+        (0x1D, 0x0, [0x1, 0x0, 0x0, 0x0]),
+        (0x1D, 0x1, [0x04002000, 0x00080040, 0x10, 0x0]),
+        (0x1E, 0x0, [0x0, 0x4010, 0x0, 0x0]),
+    ]);
+
+    let amx = AmxInfo::get_from_source(&src).unwrap();
+    let palette = &amx.palettes[0];
+    let tmul = amx.tmul.unwrap();
+
+    assert_eq!(amx.max_palette, 1);
+    assert_eq!((palette.total_tile_bytes, palette.bytes_per_tile, palette.bytes_per_row), (8192, 1024, 64));
+    assert_eq!((palette.max_names, palette.max_rows), (8, 16));
+    assert_eq!((tmul.tmul_maxk, tmul.tmul_maxn), (16, 64));
+}
@@ -0,0 +1,238 @@
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
+
+/// Architectural performance monitoring events, the bit position of `CPUID.(EAX=0Ah):EBX` and `CPUID.(EAX=23h, ECX=3):EAX`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchPerfmonEvent {
+    CoreCycles,
+    InstRetired,
+    RefCycles,
+    LlcRef,
+    LlcMisses,
+    BranchInstRetired,
+    BranchMissRetired,
+    TopdownSlots,
+    TopdownBackendBound,
+    TopdownBadSpec,
+    TopdownFrontendBound,
+    TopdownRetiring,
+    LbrInserts,
+}
+
+impl ArchPerfmonEvent {
+    pub const ALL: [Self; 13] = [
+        Self::CoreCycles,
+        Self::InstRetired,
+        Self::RefCycles,
+        Self::LlcRef,
+        Self::LlcMisses,
+        Self::BranchInstRetired,
+        Self::BranchMissRetired,
+        Self::TopdownSlots,
+        Self::TopdownBackendBound,
+        Self::TopdownBadSpec,
+        Self::TopdownFrontendBound,
+        Self::TopdownRetiring,
+        Self::LbrInserts,
+    ];
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for ArchPerfmonEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Architectural Performance Monitoring Extended, `CPUID.(EAX=23h)`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchPerfmonExt {
+    /// Sub-leaf 0, EAX: Valid sub-leaves bitmap
+    pub valid_sub_leaf: u32,
+    /// Sub-leaf 0, EBX[0]: UnitMask2 (IA32_PERFEVTSELx[47:40])
+    pub umask2: bool,
+    /// Sub-leaf 0, EBX[1]: EQ-bit (IA32_PERFEVTSELx[36])
+    pub eq_bit: bool,
+    /// Sub-leaf 1
+    pub gp_counter_bitmap: u32,
+    pub fixed_counter_bitmap: u32,
+    /// Sub-leaf 2: Auto Counter Reload, the reloadable counters
+    pub acr_gp_counter_bitmap: u32,
+    pub acr_fixed_counter_bitmap: u32,
+    /// Sub-leaf 3: Supported architectural events bitmap
+    pub events: u32,
+}
+
+impl ArchPerfmonExt {
+    /// Set the fields of the `sub_leaf`
+    pub fn update(&mut self, sub_leaf: u32, cpuid: &CpuidResult) {
+        match sub_leaf {
+            0x0 => {
+                self.valid_sub_leaf = cpuid.eax;
+                self.umask2 = (cpuid.ebx & 0b1) != 0;
+                self.eq_bit = (cpuid.ebx & 0b10) != 0;
+            },
+            0x1 => {
+                self.gp_counter_bitmap = cpuid.eax;
+                self.fixed_counter_bitmap = cpuid.ebx;
+            },
+            0x2 => {
+                self.acr_gp_counter_bitmap = cpuid.eax;
+                self.acr_fixed_counter_bitmap = cpuid.ebx;
+            },
+            0x3 => self.events = cpuid.eax,
+            _ => {},
+        }
+    }
+
+    pub fn get() -> Self {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        let mut ext = Self::default();
+        let sub0 = src.cpuid(0x23, 0x0);
+
+        ext.update(0x0, &sub0);
+
+        for sub_leaf in 0x1..=0x3 {
+            if (ext.valid_sub_leaf >> sub_leaf) & 0b1 != 0 {
+                ext.update(sub_leaf, &src.cpuid(0x23, sub_leaf));
+            }
+        }
+
+        ext
+    }
+}
+
+/// Architectural Performance Monitoring, `CPUID.(EAX=0Ah)`, and `CPUID.(EAX=23h)` if supported.
+/// On the hybrid architecture, the result differs for each core type (see [TopoPartInfo](crate::TopoPartInfo)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchPerfmon {
+    pub version: u8,
+    pub num_gp_counter: u8,
+    pub gp_counter_width: u8,
+    /// EAX[31:24]: Length of EBX bit vector to enumerate architectural performance monitoring events
+    pub event_len: u8,
+    /// EBX: set bit is the unavailable event
+    pub unavailable_events: u32,
+    /// ECX: Supported fixed counters bitmap
+    pub fixed_counter_bitmap: u32,
+    pub num_fixed_counter: u8,
+    pub fixed_counter_width: u8,
+    pub any_thread_deprecated: bool,
+    pub ext: Option<ArchPerfmonExt>,
+}
+
+impl From<&CpuidResult> for ArchPerfmon {
+    fn from(cpuid: &CpuidResult) -> Self {
+        let version = (cpuid.eax & 0xFF) as u8;
+        let num_gp_counter = ((cpuid.eax >> 8) & 0xFF) as u8;
+        let gp_counter_width = ((cpuid.eax >> 16) & 0xFF) as u8;
+        let event_len = (cpuid.eax >> 24) as u8;
+        let unavailable_events = cpuid.ebx;
+        let fixed_counter_bitmap = cpuid.ecx;
+        let num_fixed_counter = (cpuid.edx & 0x1F) as u8;
+        let fixed_counter_width = ((cpuid.edx >> 5) & 0xFF) as u8;
+        let any_thread_deprecated = ((cpuid.edx >> 15) & 0b1) != 0;
+
+        Self {
+            version,
+            num_gp_counter,
+            gp_counter_width,
+            event_len,
+            unavailable_events,
+            fixed_counter_bitmap,
+            num_fixed_counter,
+            fixed_counter_width,
+            any_thread_deprecated,
+            ext: None,
+        }
+    }
+}
+
+impl ArchPerfmon {
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if the leaf is not supported or the version ID is 0 (e.g. virtual machine without vPMU)
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        let max_leaf = src.cpuid(0x0, 0x0).eax;
+
+        if max_leaf < 0xA {
+            return None;
+        }
+
+        let mut perfmon = Self::from(&src.cpuid(0xA, 0x0));
+
+        if perfmon.version == 0 {
+            return None;
+        }
+
+        /* ArchPerfmonExt: CPUID[Leaf=0x7, SubLeaf=0x1].EAX[8] */
+        let has_ext = max_leaf >= 0x23 && ((src.cpuid(0x7, 0x1).eax >> 8) & 0b1) != 0;
+
+        if has_ext {
+            perfmon.ext = Some(ArchPerfmonExt::get_from_source(src));
+        }
+
+        Some(perfmon)
+    }
+
+    /// Bitmap of the general-purpose counters
+    pub fn gp_counter_bitmap(&self) -> u32 {
+        match &self.ext {
+            Some(ext) if (ext.valid_sub_leaf & 0b10) != 0 => ext.gp_counter_bitmap,
+            _ => !(u32::MAX.checked_shl(self.num_gp_counter as u32).unwrap_or(0)),
+        }
+    }
+
+    /// Bitmap of the fixed-function counters,
+    /// fixed counter `i` is supported if `ECX[i]` is set or `i < EDX[4:0]`
+    pub fn fixed_counter_bitmap(&self) -> u32 {
+        match &self.ext {
+            Some(ext) if (ext.valid_sub_leaf & 0b10) != 0 => ext.fixed_counter_bitmap,
+            _ => self.fixed_counter_bitmap
+                | !(u32::MAX.checked_shl(self.num_fixed_counter as u32).unwrap_or(0)),
+        }
+    }
+
+    pub fn is_event_available(&self, event: ArchPerfmonEvent) -> bool {
+        let pos = event as u32;
+
+        match &self.ext {
+            Some(ext) if (ext.valid_sub_leaf & 0b1000) != 0 => ((ext.events >> pos) & 0b1) != 0,
+            _ => pos < self.event_len as u32 && ((self.unavailable_events >> pos) & 0b1) == 0,
+        }
+    }
+
+    pub fn available_events(&self) -> impl Iterator<Item = ArchPerfmonEvent> + '_ {
+        IntoIterator::into_iter(ArchPerfmonEvent::ALL).filter(move |event| self.is_event_available(*event))
+    }
+}
+
+#[test]
+fn test_arch_perfmon() {
+    /* Core i5-12400 (Golden Cove), CPUID.(EAX=0Ah) */
+    let cpuid = CpuidResult { eax: 0x07300805, ebx: 0x00000000, ecx: 0x0000000F, edx: 0x00008603 };
+    let perfmon = ArchPerfmon::from(&cpuid);
+
+    assert_eq!(perfmon.version, 5);
+    assert_eq!((perfmon.num_gp_counter, perfmon.gp_counter_width), (8, 48));
+    assert_eq!((perfmon.num_fixed_counter, perfmon.fixed_counter_width), (3, 48));
+    assert!(perfmon.any_thread_deprecated);
+    assert_eq!(perfmon.gp_counter_bitmap(), 0xFF);
+    assert_eq!(perfmon.fixed_counter_bitmap(), 0xF);
+    assert_eq!(perfmon.available_events().count(), 7);
+    assert!(!perfmon.is_event_available(ArchPerfmonEvent::TopdownSlots));
+
+    let mut ext = ArchPerfmonExt::default();
+    ext.update(0x0, &CpuidResult { eax: 0xB, ebx: 0x3, ecx: 0x0, edx: 0x0 });
+    ext.update(0x1, &CpuidResult { eax: 0xFF, ebx: 0x77, ecx: 0x0, edx: 0x0 });
+    ext.update(0x3, &CpuidResult { eax: 0x1FFF, ebx: 0x0, ecx: 0x0, edx: 0x0 });
+
+    let perfmon = ArchPerfmon { ext: Some(ext), ..perfmon };
+
+    assert_eq!(perfmon.fixed_counter_bitmap(), 0x77);
+    assert!(perfmon.is_event_available(ArchPerfmonEvent::LbrInserts));
+}
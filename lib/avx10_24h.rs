@@ -0,0 +1,74 @@
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
+
+/// Intel AVX10 Converged Vector ISA, `CPUID.(EAX=24h, ECX=0)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Avx10Info {
+    /// EBX[7:0]: AVX10 Converged Vector ISA version (>= 1)
+    pub version: u8,
+    /// EBX[16]: 128-bit vector support
+    pub vl128: bool,
+    /// EBX[17]: 256-bit vector support
+    pub vl256: bool,
+    /// EBX[18]: 512-bit vector support
+    pub vl512: bool,
+}
+
+impl From<&CpuidResult> for Avx10Info {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            version: (cpuid.ebx & 0xFF) as u8,
+            vl128: ((cpuid.ebx >> 16) & 0b1) != 0,
+            vl256: ((cpuid.ebx >> 17) & 0b1) != 0,
+            vl512: ((cpuid.ebx >> 18) & 0b1) != 0,
+        }
+    }
+}
+
+impl Avx10Info {
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if AVX10 (`CPUID.(EAX=07h, ECX=1):EDX[19]`) is not supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if src.cpuid(0x0, 0x0).eax < 0x24
+        || src.cpuid(0x7, 0x0).eax < 0x1
+        || ((src.cpuid(0x7, 0x1).edx >> 19) & 0b1) == 0 {
+            return None;
+        }
+
+        Some(Self::from(&src.cpuid(0x24, 0x0)))
+    }
+
+    /// Maximum supported vector length in bits
+    pub fn max_vl(&self) -> u16 {
+        match (self.vl512, self.vl256, self.vl128) {
+            (true, _, _) => 512,
+            (_, true, _) => 256,
+            (_, _, true) => 128,
+            _ => 0,
+        }
+    }
+}
+
+#[test]
+fn test_avx10_info() {
+    use crate::{CpuidTable, MicroArchLevel};
+
+    /* AVX10.2/512 with the AVX-512 flags of 0x7 EBX cleared, and the x86-64-v3 flags set */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x24, 0x756E6547, 0x6C65746E, 0x49656E69]),
+        (0x1, 0x0, [0x0, 0x0, 0x7EF8320B, 0x178BFBFF]),
+        (0x7, 0x0, [0x1, 0x219C97A9, 0x0, 0x0]),
+        (0x7, 0x1, [0x0, 0x0, 0x0, 0x00080000]),
+        (0x24, 0x0, [0x0, 0x00070002, 0x0, 0x0]),
+        (0x8000_0001, 0x0, [0x0, 0x0, 0x75C237FF, 0x2FD3FBFF]),
+    ]);
+
+    let avx10 = Avx10Info::get_from_source(&src).unwrap();
+
+    assert_eq!(avx10.version, 2);
+    assert!(avx10.vl128 && avx10.vl256 && avx10.vl512);
+    assert_eq!(avx10.max_vl(), 512);
+    assert_eq!(MicroArchLevel::check_from_source(&src), MicroArchLevel::X86_64_V4);
+}
@@ -0,0 +1,257 @@
+use crate::{CacheProp, CacheType, CpuidResult, TlbAssoc, TlbInfo, Unit};
+#[cfg(feature = "std")]
+use crate::{CpuidSource, LiveCpuid};
+
+/// TLB described by the one-byte descriptor of `CPUID.(EAX=02h)`
+#[derive(Debug, Clone)]
+pub struct DescTlb {
+    pub level: u8,
+    /// `Unified` for the shared 2nd-level TLB (STLB)
+    pub tlb_type: CacheType,
+    pub page_4k: bool,
+    pub page_2m: bool,
+    pub page_4m: bool,
+    pub page_1g: bool,
+    /// `TlbAssoc::Invalid` if the descriptor does not specify the associativity
+    pub info: TlbInfo,
+}
+
+/// Legacy cache/TLB descriptor of `CPUID.(EAX=02h)`, Intel SDM Vol.2A Table 3-12
+#[derive(Debug, Clone)]
+pub enum CacheDesc {
+    /// `share_thread` is 0, the descriptor does not report it
+    Cache(CacheProp),
+    Tlb(DescTlb),
+    TraceCache { k_uops: u8, way: u8 },
+    /// Prefetch size in bytes
+    Prefetch(u16),
+    /// 0x40: No 2nd-level cache or, if processor contains a valid 2nd-level cache, no 3rd-level cache
+    NoL2OrL3,
+    /// 0xFE: Use `CPUID.(EAX=18h)` for TLB parameters
+    UseLeaf18h,
+    /// 0xFF: Use `CPUID.(EAX=04h)` for cache parameters
+    UseLeaf04h,
+    Unknown(u8),
+}
+
+const P4K: u8 = 0b0001;
+const P2M: u8 = 0b0010;
+const P4M: u8 = 0b0100;
+const P1G: u8 = 0b1000;
+
+fn cache(level: u32, cache_type: CacheType, size_kib: u32, way: u32, line_size: u32) -> CacheDesc {
+    let size = size_kib * Unit::KiB.to_byte();
+
+    CacheDesc::Cache(CacheProp {
+        cache_type,
+        level,
+        line_size,
+        way,
+        set: size / (way * line_size),
+        size,
+        size_unit: Unit::from(size),
+        share_thread: 0,
+        inclusive: false,
+    })
+}
+
+fn tlb(level: u8, tlb_type: CacheType, pages: u8, size: u16, assoc: TlbAssoc) -> CacheDesc {
+    CacheDesc::Tlb(DescTlb {
+        level,
+        tlb_type,
+        page_4k: (pages & P4K) != 0,
+        page_2m: (pages & P2M) != 0,
+        page_4m: (pages & P4M) != 0,
+        page_1g: (pages & P1G) != 0,
+        info: TlbInfo { size, assoc },
+    })
+}
+
+impl CacheDesc {
+    /// Decode the one-byte descriptor, 0x00 (null descriptor) is `None`.
+    /// Some descriptors (0x63, 0xC3) describe two TLBs.
+    pub fn decode(desc: u8) -> [Option<Self>; 2] {
+        use CacheType::{Data as D, Instruction as I, Unified as U};
+        use TlbAssoc::{Full, Invalid, Way};
+
+        let v = match desc {
+            0x00 => return [None, None],
+            0x01 => tlb(1, I, P4K, 32, Way(4)),
+            0x02 => tlb(1, I, P4M, 2, Full),
+            0x03 => tlb(1, D, P4K, 64, Way(4)),
+            0x04 => tlb(1, D, P4M, 8, Way(4)),
+            0x05 => tlb(1, D, P4M, 32, Way(4)),
+            0x06 => cache(1, I, 8, 4, 32),
+            0x08 => cache(1, I, 16, 4, 32),
+            0x09 => cache(1, I, 32, 4, 64),
+            0x0A => cache(1, D, 8, 2, 32),
+            0x0B => tlb(1, I, P4M, 4, Way(4)),
+            0x0C => cache(1, D, 16, 4, 32),
+            0x0D => cache(1, D, 16, 4, 64),
+            0x0E => cache(1, D, 24, 6, 64),
+            0x1D => cache(2, U, 128, 2, 64),
+            0x21 => cache(2, U, 256, 8, 64),
+            0x22 => cache(3, U, 512, 4, 64),
+            0x23 => cache(3, U, 1024, 8, 64),
+            0x24 => cache(2, U, 1024, 16, 64),
+            0x25 => cache(3, U, 2048, 8, 64),
+            0x29 => cache(3, U, 4096, 8, 64),
+            0x2C => cache(1, D, 32, 8, 64),
+            0x30 => cache(1, I, 32, 8, 64),
+            0x40 => Self::NoL2OrL3,
+            0x41 => cache(2, U, 128, 4, 32),
+            0x42 => cache(2, U, 256, 4, 32),
+            0x43 => cache(2, U, 512, 4, 32),
+            0x44 => cache(2, U, 1024, 4, 32),
+            0x45 => cache(2, U, 2048, 4, 32),
+            0x46 => cache(3, U, 4096, 4, 64),
+            0x47 => cache(3, U, 8192, 8, 64),
+            0x48 => cache(2, U, 3072, 12, 64),
+            /* 3rd-level cache on Intel Xeon processor MP (Family 0FH, Model 06H) */
+            0x49 => cache(2, U, 4096, 16, 64),
+            0x4A => cache(3, U, 6144, 12, 64),
+            0x4B => cache(3, U, 8192, 16, 64),
+            0x4C => cache(3, U, 12288, 12, 64),
+            0x4D => cache(3, U, 16384, 16, 64),
+            0x4E => cache(2, U, 6144, 24, 64),
+            0x4F => tlb(1, I, P4K, 32, Invalid),
+            0x50 => tlb(1, I, P4K | P2M | P4M, 64, Invalid),
+            0x51 => tlb(1, I, P4K | P2M | P4M, 128, Invalid),
+            0x52 => tlb(1, I, P4K | P2M | P4M, 256, Invalid),
+            0x55 => tlb(1, I, P2M | P4M, 7, Full),
+            0x56 => tlb(1, D, P4M, 16, Way(4)),
+            0x57 => tlb(1, D, P4K, 16, Way(4)),
+            0x59 => tlb(1, D, P4K, 16, Full),
+            0x5A => tlb(1, D, P2M | P4M, 32, Way(4)),
+            0x5B => tlb(1, D, P4K | P4M, 64, Invalid),
+            0x5C => tlb(1, D, P4K | P4M, 128, Invalid),
+            0x5D => tlb(1, D, P4K | P4M, 256, Invalid),
+            0x60 => cache(1, D, 16, 8, 64),
+            0x61 => tlb(1, I, P4K, 48, Full),
+            0x63 => return [
+                Some(tlb(1, D, P2M | P4M, 32, Way(4))),
+                Some(tlb(1, D, P1G, 4, Way(4))),
+            ],
+            0x64 => tlb(1, D, P4K, 512, Way(4)),
+            0x66 => cache(1, D, 8, 4, 64),
+            0x67 => cache(1, D, 16, 4, 64),
+            0x68 => cache(1, D, 32, 4, 64),
+            0x6A => tlb(1, D, P4K, 64, Way(8)),
+            0x6B => tlb(1, D, P4K, 256, Way(8)),
+            0x6C => tlb(1, D, P2M | P4M, 128, Way(8)),
+            0x6D => tlb(1, D, P1G, 16, Full),
+            0x70 => Self::TraceCache { k_uops: 12, way: 8 },
+            0x71 => Self::TraceCache { k_uops: 16, way: 8 },
+            0x72 => Self::TraceCache { k_uops: 32, way: 8 },
+            0x76 => tlb(1, I, P2M | P4M, 8, Full),
+            0x78 => cache(2, U, 1024, 4, 64),
+            0x79 => cache(2, U, 128, 8, 64),
+            0x7A => cache(2, U, 256, 8, 64),
+            0x7B => cache(2, U, 512, 8, 64),
+            0x7C => cache(2, U, 1024, 8, 64),
+            0x7D => cache(2, U, 2048, 8, 64),
+            0x7F => cache(2, U, 512, 2, 64),
+            0x80 => cache(2, U, 512, 8, 64),
+            0x82 => cache(2, U, 256, 8, 32),
+            0x83 => cache(2, U, 512, 8, 32),
+            0x84 => cache(2, U, 1024, 8, 32),
+            0x85 => cache(2, U, 2048, 8, 32),
+            0x86 => cache(2, U, 512, 4, 64),
+            0x87 => cache(2, U, 1024, 8, 64),
+            0xA0 => tlb(1, D, P4K, 32, Full),
+            0xB0 => tlb(1, I, P4K, 128, Way(4)),
+            /* 8 entries for 2M pages, or 4 entries for 4M pages */
+            0xB1 => tlb(1, I, P2M | P4M, 8, Way(4)),
+            0xB2 => tlb(1, I, P4K, 64, Way(4)),
+            0xB3 => tlb(1, D, P4K, 128, Way(4)),
+            0xB4 => tlb(1, D, P4K, 256, Way(4)),
+            0xB5 => tlb(1, I, P4K, 64, Way(8)),
+            0xB6 => tlb(1, I, P4K, 128, Way(8)),
+            0xBA => tlb(1, D, P4K, 64, Way(4)),
+            0xC0 => tlb(1, D, P4K | P4M, 8, Way(4)),
+            0xC1 => tlb(2, U, P4K | P2M, 1024, Way(8)),
+            0xC2 => tlb(1, D, P4K | P2M, 16, Way(4)),
+            0xC3 => return [
+                Some(tlb(2, U, P4K | P2M, 1536, Way(6))),
+                Some(tlb(2, U, P1G, 16, Way(4))),
+            ],
+            0xC4 => tlb(1, D, P2M | P4M, 32, Way(4)),
+            0xCA => tlb(2, U, P4K, 512, Way(4)),
+            0xD0 => cache(3, U, 512, 4, 64),
+            0xD1 => cache(3, U, 1024, 4, 64),
+            0xD2 => cache(3, U, 2048, 4, 64),
+            0xD6 => cache(3, U, 1024, 8, 64),
+            0xD7 => cache(3, U, 2048, 8, 64),
+            0xD8 => cache(3, U, 4096, 8, 64),
+            0xDC => cache(3, U, 1536, 12, 64),
+            0xDD => cache(3, U, 3072, 12, 64),
+            0xDE => cache(3, U, 6144, 12, 64),
+            0xE2 => cache(3, U, 2048, 16, 64),
+            0xE3 => cache(3, U, 4096, 16, 64),
+            0xE4 => cache(3, U, 8192, 16, 64),
+            0xEA => cache(3, U, 12288, 24, 64),
+            0xEB => cache(3, U, 18432, 24, 64),
+            0xEC => cache(3, U, 24576, 24, 64),
+            0xF0 => Self::Prefetch(64),
+            0xF1 => Self::Prefetch(128),
+            0xFE => Self::UseLeaf18h,
+            0xFF => Self::UseLeaf04h,
+            _ => Self::Unknown(desc),
+        };
+
+        [Some(v), None]
+    }
+
+    /// Descriptor bytes of `CPUID.(EAX=02h)`, except EAX[7:0] (always 0x01) and the registers with bit 31 set (reserved).
+    pub fn desc_bytes(cpuid: &CpuidResult) -> impl Iterator<Item = u8> {
+        let CpuidResult { eax, ebx, ecx, edx } = *cpuid;
+
+        IntoIterator::into_iter([eax & !0xFF, ebx, ecx, edx])
+            .filter(|reg| (reg >> 31) == 0)
+            .flat_map(|reg| reg.to_le_bytes())
+            .filter(|desc| *desc != 0x00)
+    }
+
+    pub fn iter_from_cpuid(cpuid: &CpuidResult) -> impl Iterator<Item = Self> {
+        Self::desc_bytes(cpuid).flat_map(Self::decode).flatten()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn get() -> Vec<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Vec<Self> {
+        Self::iter_from_cpuid(&src.cpuid(0x2, 0x0)).collect()
+    }
+}
+
+#[test]
+fn test_cache_desc() {
+    /* Intel SDM Vol.2A, Example 3-1 (Pentium 4) */
+    let cpuid = CpuidResult { eax: 0x665B5001, ebx: 0x0, ecx: 0x0, edx: 0x007A7000 };
+
+    let bytes: Vec<u8> = CacheDesc::desc_bytes(&cpuid).collect();
+    assert_eq!(bytes, [0x50, 0x5B, 0x66, 0x70, 0x7A]);
+
+    let desc: Vec<CacheDesc> = CacheDesc::iter_from_cpuid(&cpuid).collect();
+    assert_eq!(desc.len(), 5);
+
+    let CacheDesc::Cache(ref l1d) = desc[2] else { panic!("{:?}", desc[2]) };
+    assert_eq!((l1d.level, l1d.cache_type, l1d.way, l1d.set), (1, CacheType::Data, 4, 32));
+    assert_eq!((l1d.size_in_the_unit(), l1d.size_unit), (8.0, Unit::KiB));
+
+    let CacheDesc::Tlb(ref dtlb) = desc[1] else { panic!("{:?}", desc[1]) };
+    assert_eq!((dtlb.level, dtlb.tlb_type, dtlb.info.size), (1, CacheType::Data, 64));
+    assert!(dtlb.page_4k && dtlb.page_4m && !dtlb.page_2m);
+
+    /* Sapphire Rapids, register with bit 31 set is reserved */
+    let cpuid = CpuidResult { eax: 0x00FEFF01, ebx: 0x000000F0, ecx: 0x0, edx: 0x80000000 };
+    let desc: Vec<CacheDesc> = CacheDesc::iter_from_cpuid(&cpuid).collect();
+
+    assert!(matches!(
+        desc[..],
+        [CacheDesc::UseLeaf04h, CacheDesc::UseLeaf18h, CacheDesc::Prefetch(64)],
+    ));
+}
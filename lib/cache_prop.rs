@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult, CpuVendor};
+use crate::{CpuidResult, CpuidSource, CpuVendor, LiveCpuid};
 
 /// Used for [CacheProp]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -122,10 +122,14 @@ impl From<&CpuidResult> for CacheProp {
 
 impl CacheProp {
     pub fn get_cache_prop_leaf() -> Option<u32> {
-        match CpuVendor::get() {
+        Self::get_cache_prop_leaf_from_source(&LiveCpuid)
+    }
+
+    pub fn get_cache_prop_leaf_from_source<S: CpuidSource>(src: &S) -> Option<u32> {
+        match CpuVendor::get_from_source(src) {
             CpuVendor::AuthenticAMD => {
                 /* AMD TopologyExtensions: CPUID[Leaf=0x8000_0001, SubLeaf=0x0].ECX[22] */
-                let amd_topo_ext = ((cpuid!(0x8000_0001, 0x0).ecx >> 22) & 0b1) != 0;
+                let amd_topo_ext = ((src.cpuid(0x8000_0001, 0x0).ecx >> 22) & 0b1) != 0;
 
                 if amd_topo_ext {
                     Some(0x8000_001D)
@@ -160,10 +164,18 @@ impl CacheProp {
     }
 
     pub fn get(level: u32, cache_type: CacheType) -> Option<Self> {
-        let leaf = Self::get_cache_prop_leaf()?;
+        Self::get_from_source(&LiveCpuid, level, cache_type)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(
+        src: &S,
+        level: u32,
+        cache_type: CacheType,
+    ) -> Option<Self> {
+        let leaf = Self::get_cache_prop_leaf_from_source(src)?;
 
         for sub_leaf in 0..5 {
-            let prop = Self::from(&cpuid!(leaf, sub_leaf));
+            let prop = Self::from(&src.cpuid(leaf, sub_leaf));
 
             if prop.level == level && prop.cache_type == cache_type {
                 return Some(prop);
@@ -915,7 +915,8 @@ impl IntelMicroArch {
     }
 
     pub fn is_atom(&self) -> bool {
-        match self {
+        matches!(
+            self,
             Self::Bonnell |
             Self::Saltwell |
             Self::Silvermont |
@@ -924,9 +925,8 @@ impl IntelMicroArch {
             Self::GoldmontPlus |
             Self::Tremont |
             Self::Gracemont |
-            Self::Crestmont => true,
-            _ => false,
-        }
+            Self::Crestmont
+        )
     }
 
     pub fn is_core(&self) -> bool {
@@ -934,11 +934,7 @@ impl IntelMicroArch {
     }
 
     pub fn is_hybrid(&self) -> bool {
-        if let Self::Hybrid(_, _) = *self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Self::Hybrid(_, _))
     }
 }
 
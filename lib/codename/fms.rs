@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult, CpuVendor};
+use crate::{CpuidResult, CpuidSource, CpuVendor, LiveCpuid};
 use crate::codename::{AmdCodename, IntelCodename, ZhaoxinCodename};
 use crate::codename::{AmdMicroArch, IntelMicroArch, ZhaoxinMicroArch};
 #[cfg(feature = "std")]
@@ -7,7 +7,7 @@ use std::fmt;
 impl ProcInfo {
     pub fn from_fms(fms: &FamModStep, vendor: &CpuVendor) -> Self {
         let [f, m, s] = [fms.syn_fam, fms.syn_mod, fms.step];
-        let vendor = vendor.clone();
+        let vendor = *vendor;
 
         macro_rules! unknown {
             ($vendor: expr, $family: expr, $model: expr, $step: expr) => {
@@ -212,6 +212,10 @@ impl From<&CpuidResult> for FamModStep {
 
 impl FamModStep {
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x1))
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x1, 0x0))
     }
 }
@@ -0,0 +1,240 @@
+use crate::CpuidResult;
+#[cfg(feature = "std")]
+use crate::{AmdMemEncrypt, CpuidSource, HypervisorInfo, LiveCpuid};
+
+/// Key Locker, `CPUID.(EAX=19h)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyLockerInfo {
+    /// EAX[0]: CPL0-only restriction
+    pub cpl0_only: bool,
+    /// EAX[1]: No-encrypt restriction
+    pub no_encrypt: bool,
+    /// EAX[2]: No-decrypt restriction
+    pub no_decrypt: bool,
+    /// EBX[0]: AESKLE, the AES Key Locker instructions are fully enabled
+    pub aeskle: bool,
+    /// EBX[2]: AES wide Key Locker instructions
+    pub wide: bool,
+    /// EBX[4]: IWKey backup MSRs
+    pub iwkey_backup: bool,
+    /// ECX[0]: NoBackup parameter to LOADIWKEY
+    pub no_backup: bool,
+    /// ECX[1]: KeySource encoding of 1 (randomization of the internal wrapping key)
+    pub random_iwkey: bool,
+}
+
+impl From<&CpuidResult> for KeyLockerInfo {
+    fn from(cpuid: &CpuidResult) -> Self {
+        let bit = |reg: u32, pos: u32| ((reg >> pos) & 0b1) != 0;
+
+        Self {
+            cpl0_only: bit(cpuid.eax, 0),
+            no_encrypt: bit(cpuid.eax, 1),
+            no_decrypt: bit(cpuid.eax, 2),
+            aeskle: bit(cpuid.ebx, 0),
+            wide: bit(cpuid.ebx, 2),
+            iwkey_backup: bit(cpuid.ebx, 4),
+            no_backup: bit(cpuid.ecx, 0),
+            random_iwkey: bit(cpuid.ecx, 1),
+        }
+    }
+}
+
+/// Target of the PCONFIG instruction, `CPUID.(EAX=1Bh, ECX=n)`: EBX, ECX, EDX
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PconfigTarget {
+    /// Multi-Key Total Memory Encryption
+    Mktme,
+    Unknown(u32),
+}
+
+impl From<u32> for PconfigTarget {
+    fn from(id: u32) -> Self {
+        match id {
+            0x1 => Self::Mktme,
+            _ => Self::Unknown(id),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for PconfigTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl PconfigTarget {
+    /// Target identifiers of the sub-leaf, `None` if the sub-leaf type (EAX[11:0]) is invalid
+    pub fn option_from_cpuid(cpuid: &CpuidResult) -> Option<[Option<Self>; 3]> {
+        /* Sub-leaf type 1: Target Identifier */
+        if (cpuid.eax & 0xFFF) != 0x1 {
+            return None;
+        }
+
+        Some([cpuid.ebx, cpuid.ecx, cpuid.edx].map(|id| if id != 0 { Some(Self::from(id)) } else { None }))
+    }
+}
+
+/// Confidential guest detected by CPUID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CcGuest {
+    /// Intel TDX guest: `CPUID.(EAX=21h, ECX=0)` returns "IntelTDX    "
+    Tdx,
+}
+
+impl CcGuest {
+    /// "IntelTDX    ": EBX, EDX, ECX of `CPUID.(EAX=21h, ECX=0)`
+    pub const TDX_SIGNATURE: [u32; 3] = [0x6574_6E49, 0x5844_546C, 0x2020_2020];
+
+    pub fn is_tdx_signature(cpuid: &CpuidResult) -> bool {
+        [cpuid.ebx, cpuid.edx, cpuid.ecx] == Self::TDX_SIGNATURE
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for CcGuest {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Summary of the memory encryption and confidential computing capabilities of AMD and Intel.
+/// Intel SEAM (TDX host) is not enumerated by CPUID, but by IA32_MTRRCAP\[15\].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfidentialComputing {
+    /// AMD Secure Memory Encryption: `CPUID.(EAX=8000_001Fh):EAX[0]`
+    pub sme: bool,
+    /// AMD Secure Encrypted Virtualization: `CPUID.(EAX=8000_001Fh):EAX[1]`
+    pub sev: bool,
+    /// SEV Encrypted State: `CPUID.(EAX=8000_001Fh):EAX[3]`
+    pub sev_es: bool,
+    /// SEV Secure Nested Paging: `CPUID.(EAX=8000_001Fh):EAX[4]`
+    pub sev_snp: bool,
+    /// Page table bit number used to enable memory encryption: `CPUID.(EAX=8000_001Fh):EBX[5:0]`
+    pub c_bit: u8,
+    /// Number of VM Permission Levels supported: `CPUID.(EAX=8000_001Fh):EBX[15:12]`
+    pub num_vmpl: u8,
+    /// Intel Total Memory Encryption: `CPUID.(EAX=07h, ECX=0):ECX[13]`
+    pub tme: bool,
+    /// PCONFIG targets, `CPUID.(EAX=1Bh)`, MKTME if supported
+    pub pconfig_targets: Vec<PconfigTarget>,
+    /// `CPUID.(EAX=19h)`, `None` if Key Locker (`CPUID.(EAX=07h, ECX=0):ECX[23]`) is not supported
+    pub key_locker: Option<KeyLockerInfo>,
+    /// `None` if not running inside a confidential guest
+    pub guest: Option<CcGuest>,
+    /// The hypervisor exposes AMD SEV to the guest.
+    /// Whether SEV is active can only be confirmed by the SEV_STATUS MSR (C001_0131h), not by CPUID.
+    pub sev_exposed_to_guest: bool,
+}
+
+#[cfg(feature = "std")]
+impl ConfidentialComputing {
+    pub fn get() -> Self {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        let max_std_leaf = src.cpuid(0x0, 0x0).eax;
+        let bit = |reg: u32, pos: u32| ((reg >> pos) & 0b1) != 0;
+
+        let amd = AmdMemEncrypt::get_from_source(src);
+        let sev = amd.as_ref().is_some_and(|amd| amd.sev());
+
+        let leaf_07h = if 0x7 <= max_std_leaf {
+            src.cpuid(0x7, 0x0)
+        } else {
+            CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 }
+        };
+
+        let pconfig_targets = if 0x1B <= max_std_leaf && bit(leaf_07h.edx, 18) {
+            (0x0..0x40)
+                .map(|sub_leaf| PconfigTarget::option_from_cpuid(&src.cpuid(0x1B, sub_leaf)))
+                .take_while(|targets| targets.is_some())
+                .flatten()
+                .flat_map(|targets| IntoIterator::into_iter(targets).flatten())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let key_locker = if 0x19 <= max_std_leaf && bit(leaf_07h.ecx, 23) {
+            Some(KeyLockerInfo::from(&src.cpuid(0x19, 0x0)))
+        } else {
+            None
+        };
+
+        let guest = if 0x21 <= max_std_leaf && CcGuest::is_tdx_signature(&src.cpuid(0x21, 0x0)) {
+            Some(CcGuest::Tdx)
+        } else {
+            None
+        };
+
+        Self {
+            sme: amd.as_ref().is_some_and(|amd| amd.sme()),
+            sev,
+            sev_es: amd.as_ref().is_some_and(|amd| amd.sev_es()),
+            sev_snp: amd.as_ref().is_some_and(|amd| amd.sev_snp()),
+            c_bit: amd.as_ref().map_or(0, |amd| amd.c_bit),
+            num_vmpl: amd.as_ref().map_or(0, |amd| amd.num_vmpl),
+            tme: bit(leaf_07h.ecx, 13),
+            pconfig_targets,
+            key_locker,
+            guest,
+            sev_exposed_to_guest: sev && HypervisorInfo::is_present(src),
+        }
+    }
+
+    pub fn mktme(&self) -> bool {
+        self.pconfig_targets.contains(&PconfigTarget::Mktme)
+    }
+}
+
+#[test]
+fn test_confidential_computing() {
+    use crate::CpuidTable;
+
+    /* TDX guest with TME and MKTME */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x21, 0x756E6547, 0x6C65746E, 0x49656E69]),
+        (0x1, 0x0, [0x0, 0x0, 0x80000000, 0x0]),
+        (0x7, 0x0, [0x0, 0x0, 0x00002000, 0x00040000]),
+        (0x1B, 0x0, [0x1, 0x1, 0x0, 0x0]),
+        (0x21, 0x0, [0x0, 0x65746E49, 0x20202020, 0x5844546C]),
+    ]);
+    let cc = ConfidentialComputing::get_from_source(&src);
+
+    assert!(cc.tme && cc.mktme() && !cc.sev);
+    assert!(cc.key_locker.is_none());
+    assert_eq!(cc.guest, Some(CcGuest::Tdx));
+
+    /* SEV-SNP host */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x10, 0x68747541, 0x444D4163, 0x69746E65]),
+        (0x8000_0000, 0x0, [0x8000_0028, 0x0, 0x0, 0x0]),
+        (0x8000_001F, 0x0, [0x0101FD3F, 0x00004173, 0x000003EE, 0x00000001]),
+    ]);
+    let cc = ConfidentialComputing::get_from_source(&src);
+
+    assert!(cc.sme && cc.sev && cc.sev_es && cc.sev_snp && !cc.tme);
+    assert_eq!((cc.c_bit, cc.num_vmpl), (51, 4));
+    assert!(cc.guest.is_none() && !cc.sev_exposed_to_guest);
+
+    /* any VM on the SEV host, not confirmed as a confidential guest */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x10, 0x68747541, 0x444D4163, 0x69746E65]),
+        (0x1, 0x0, [0x0, 0x0, 0x80000000, 0x0]),
+        (0x8000_0000, 0x0, [0x8000_0028, 0x0, 0x0, 0x0]),
+        (0x8000_001F, 0x0, [0x0101FD3F, 0x00004173, 0x000003EE, 0x00000001]),
+    ]);
+    let cc = ConfidentialComputing::get_from_source(&src);
+
+    assert!(cc.guest.is_none() && cc.sev_exposed_to_guest);
+
+    /* CPUID Ryzen 5 5600G, SME, SEV and SEV-ES */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let cc = ConfidentialComputing::get_from_source(&CpuidTable::from_raw_dump(dump));
+
+    assert!(cc.sme && cc.sev && cc.sev_es && !cc.sev_snp);
+    assert!(cc.guest.is_none() && !cc.sev_exposed_to_guest);
+}
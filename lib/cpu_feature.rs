@@ -0,0 +1,617 @@
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
+use core::str::FromStr;
+use core::ops::{BitAnd, BitOr, Sub};
+
+/// Output register of CPUID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuidReg {
+    EAX,
+    EBX,
+    ECX,
+    EDX,
+}
+
+impl CpuidReg {
+    pub fn get(&self, cpuid: &CpuidResult) -> u32 {
+        match self {
+            Self::EAX => cpuid.eax,
+            Self::EBX => cpuid.ebx,
+            Self::ECX => cpuid.ecx,
+            Self::EDX => cpuid.edx,
+        }
+    }
+}
+
+/// Location of a feature flag in CPUID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuFeatureLoc {
+    pub leaf: u32,
+    pub sub_leaf: u32,
+    pub reg: CpuidReg,
+    pub bit: u32,
+}
+
+macro_rules! cpu_feature {
+    ($( $ftr:ident = ($leaf:expr, $sub_leaf:expr, $reg:ident, $bit:expr), )*) => {
+        /// Feature flags of CPUID
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum CpuFeature {
+            $( $ftr, )*
+        }
+
+        impl CpuFeature {
+            /// All feature flags, sorted by Leaf/Sub-leaf
+            pub const ALL: &'static [Self] = &[ $( Self::$ftr, )* ];
+
+            pub const fn loc(&self) -> CpuFeatureLoc {
+                match self {
+                    $( Self::$ftr => CpuFeatureLoc {
+                        leaf: $leaf,
+                        sub_leaf: $sub_leaf,
+                        reg: CpuidReg::$reg,
+                        bit: $bit,
+                    }, )*
+                }
+            }
+
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    $( Self::$ftr => stringify!($ftr), )*
+                }
+            }
+        }
+    };
+}
+
+cpu_feature! {
+    /* 00_01_ECX */
+    SSE3 = (0x1, 0x0, ECX, 0),
+    PCLMULQDQ = (0x1, 0x0, ECX, 1),
+    DTES64 = (0x1, 0x0, ECX, 2),
+    MONITOR = (0x1, 0x0, ECX, 3),
+    DS_CPL = (0x1, 0x0, ECX, 4),
+    VMX = (0x1, 0x0, ECX, 5),
+    SMX = (0x1, 0x0, ECX, 6),
+    EST = (0x1, 0x0, ECX, 7),
+    TM2 = (0x1, 0x0, ECX, 8),
+    SSSE3 = (0x1, 0x0, ECX, 9),
+    CNXT_ID = (0x1, 0x0, ECX, 10),
+    SDBG = (0x1, 0x0, ECX, 11),
+    FMA = (0x1, 0x0, ECX, 12),
+    CX16 = (0x1, 0x0, ECX, 13),
+    XTPR = (0x1, 0x0, ECX, 14),
+    PDCM = (0x1, 0x0, ECX, 15),
+    PCID = (0x1, 0x0, ECX, 17),
+    DCA = (0x1, 0x0, ECX, 18),
+    SSE4_1 = (0x1, 0x0, ECX, 19),
+    SSE4_2 = (0x1, 0x0, ECX, 20),
+    X2APIC = (0x1, 0x0, ECX, 21),
+    MOVBE = (0x1, 0x0, ECX, 22),
+    POPCNT = (0x1, 0x0, ECX, 23),
+    TSC_DEADLINE = (0x1, 0x0, ECX, 24),
+    AES = (0x1, 0x0, ECX, 25),
+    XSAVE = (0x1, 0x0, ECX, 26),
+    OSXSAVE = (0x1, 0x0, ECX, 27),
+    AVX = (0x1, 0x0, ECX, 28),
+    F16C = (0x1, 0x0, ECX, 29),
+    RDRAND = (0x1, 0x0, ECX, 30),
+    HYPERVISOR = (0x1, 0x0, ECX, 31),
+    /* 00_01_EDX */
+    FPU = (0x1, 0x0, EDX, 0),
+    VME = (0x1, 0x0, EDX, 1),
+    DE = (0x1, 0x0, EDX, 2),
+    PSE = (0x1, 0x0, EDX, 3),
+    TSC = (0x1, 0x0, EDX, 4),
+    MSR = (0x1, 0x0, EDX, 5),
+    PAE = (0x1, 0x0, EDX, 6),
+    MCE = (0x1, 0x0, EDX, 7),
+    CX8 = (0x1, 0x0, EDX, 8),
+    APIC = (0x1, 0x0, EDX, 9),
+    SEP = (0x1, 0x0, EDX, 11),
+    MTRR = (0x1, 0x0, EDX, 12),
+    PGE = (0x1, 0x0, EDX, 13),
+    MCA = (0x1, 0x0, EDX, 14),
+    CMOV = (0x1, 0x0, EDX, 15),
+    PAT = (0x1, 0x0, EDX, 16),
+    PSE36 = (0x1, 0x0, EDX, 17),
+    PSN = (0x1, 0x0, EDX, 18),
+    CLFLUSH = (0x1, 0x0, EDX, 19),
+    DS = (0x1, 0x0, EDX, 21),
+    ACPI = (0x1, 0x0, EDX, 22),
+    MMX = (0x1, 0x0, EDX, 23),
+    FXSR = (0x1, 0x0, EDX, 24),
+    SSE = (0x1, 0x0, EDX, 25),
+    SSE2 = (0x1, 0x0, EDX, 26),
+    SS = (0x1, 0x0, EDX, 27),
+    HTT = (0x1, 0x0, EDX, 28),
+    TM = (0x1, 0x0, EDX, 29),
+    PBE = (0x1, 0x0, EDX, 31),
+    /* 00_06_EAX */
+    DTS = (0x6, 0x0, EAX, 0),
+    TURBO_BOOST = (0x6, 0x0, EAX, 1),
+    ARAT = (0x6, 0x0, EAX, 2),
+    PLN = (0x6, 0x0, EAX, 4),
+    ECMD = (0x6, 0x0, EAX, 5),
+    PTM = (0x6, 0x0, EAX, 6),
+    HWP = (0x6, 0x0, EAX, 7),
+    HDC = (0x6, 0x0, EAX, 13),
+    TURBO_BOOST_MAX = (0x6, 0x0, EAX, 14),
+    HFI = (0x6, 0x0, EAX, 19),
+    /* 00_07_EBX_x0 */
+    FSGSBASE = (0x7, 0x0, EBX, 0),
+    TSC_ADJUST = (0x7, 0x0, EBX, 1),
+    SGX = (0x7, 0x0, EBX, 2),
+    BMI1 = (0x7, 0x0, EBX, 3),
+    HLE = (0x7, 0x0, EBX, 4),
+    AVX2 = (0x7, 0x0, EBX, 5),
+    FDP_EXCPTN_ONLY = (0x7, 0x0, EBX, 6),
+    SMEP = (0x7, 0x0, EBX, 7),
+    BMI2 = (0x7, 0x0, EBX, 8),
+    ERMS = (0x7, 0x0, EBX, 9),
+    INVPCID = (0x7, 0x0, EBX, 10),
+    RTM = (0x7, 0x0, EBX, 11),
+    PQM = (0x7, 0x0, EBX, 12),
+    FPU_CS_DS = (0x7, 0x0, EBX, 13),
+    MPX = (0x7, 0x0, EBX, 14),
+    PQE = (0x7, 0x0, EBX, 15),
+    AVX512F = (0x7, 0x0, EBX, 16),
+    AVX512DQ = (0x7, 0x0, EBX, 17),
+    RDSEED = (0x7, 0x0, EBX, 18),
+    ADX = (0x7, 0x0, EBX, 19),
+    SMAP = (0x7, 0x0, EBX, 20),
+    AVX512_IFMA = (0x7, 0x0, EBX, 21),
+    CLFLUSHOPT = (0x7, 0x0, EBX, 23),
+    CLWB = (0x7, 0x0, EBX, 24),
+    PT = (0x7, 0x0, EBX, 25),
+    AVX512PF = (0x7, 0x0, EBX, 26),
+    AVX512ER = (0x7, 0x0, EBX, 27),
+    AVX512CD = (0x7, 0x0, EBX, 28),
+    SHA = (0x7, 0x0, EBX, 29),
+    AVX512BW = (0x7, 0x0, EBX, 30),
+    AVX512VL = (0x7, 0x0, EBX, 31),
+    /* 00_07_ECX_x0 */
+    PREFETCHWT1 = (0x7, 0x0, ECX, 0),
+    AVX512_VBMI = (0x7, 0x0, ECX, 1),
+    UMIP = (0x7, 0x0, ECX, 2),
+    PKU = (0x7, 0x0, ECX, 3),
+    OSPKE = (0x7, 0x0, ECX, 4),
+    WAITPKG = (0x7, 0x0, ECX, 5),
+    AVX512_VBMI2 = (0x7, 0x0, ECX, 6),
+    CET_SS = (0x7, 0x0, ECX, 7),
+    GFNI = (0x7, 0x0, ECX, 8),
+    VAES = (0x7, 0x0, ECX, 9),
+    VPCLMULQDQ = (0x7, 0x0, ECX, 10),
+    AVX512_VNNI = (0x7, 0x0, ECX, 11),
+    AVX512_BITALG = (0x7, 0x0, ECX, 12),
+    TME_EN = (0x7, 0x0, ECX, 13),
+    AVX512_VPOPCNTDQ = (0x7, 0x0, ECX, 14),
+    LA57 = (0x7, 0x0, ECX, 16),
+    RDPID = (0x7, 0x0, ECX, 22),
+    KL = (0x7, 0x0, ECX, 23),
+    BUS_LOCK_DETECT = (0x7, 0x0, ECX, 24),
+    CLDEMOTE = (0x7, 0x0, ECX, 25),
+    MOVDIRI = (0x7, 0x0, ECX, 27),
+    MOVDIR64B = (0x7, 0x0, ECX, 28),
+    ENQCMD = (0x7, 0x0, ECX, 29),
+    SGX_LC = (0x7, 0x0, ECX, 30),
+    PKS = (0x7, 0x0, ECX, 31),
+    /* 00_07_EDX_x0 */
+    AVX512_4VNNIW = (0x7, 0x0, EDX, 2),
+    AVX512_4FMAPS = (0x7, 0x0, EDX, 3),
+    FSRM = (0x7, 0x0, EDX, 4),
+    UINTR = (0x7, 0x0, EDX, 5),
+    AVX512_VP2INTERSECT = (0x7, 0x0, EDX, 8),
+    SRBDS_CTRL = (0x7, 0x0, EDX, 9),
+    MD_CLEAR = (0x7, 0x0, EDX, 10),
+    RTM_ALWAYS_ABORT = (0x7, 0x0, EDX, 11),
+    RTM_FORCE_ABORT = (0x7, 0x0, EDX, 13),
+    SERIALIZE = (0x7, 0x0, EDX, 14),
+    HYBRID = (0x7, 0x0, EDX, 15),
+    TSXLDTRK = (0x7, 0x0, EDX, 16),
+    PCONFIG = (0x7, 0x0, EDX, 18),
+    ARCH_LBR = (0x7, 0x0, EDX, 19),
+    CET_IBT = (0x7, 0x0, EDX, 20),
+    AMX_BF16 = (0x7, 0x0, EDX, 22),
+    AVX512_FP16 = (0x7, 0x0, EDX, 23),
+    AMX_TILE = (0x7, 0x0, EDX, 24),
+    AMX_INT8 = (0x7, 0x0, EDX, 25),
+    IBRS_IBPB = (0x7, 0x0, EDX, 26),
+    STIBP = (0x7, 0x0, EDX, 27),
+    L1D_FLUSH = (0x7, 0x0, EDX, 28),
+    ARCH_CAPABILITIES = (0x7, 0x0, EDX, 29),
+    CORE_CAPABILITIES = (0x7, 0x0, EDX, 30),
+    SSBD = (0x7, 0x0, EDX, 31),
+    /* 00_07_EAX_x1 */
+    SHA512 = (0x7, 0x1, EAX, 0),
+    SM3 = (0x7, 0x1, EAX, 1),
+    SM4 = (0x7, 0x1, EAX, 2),
+    RAO_INT = (0x7, 0x1, EAX, 3),
+    AVX_VNNI = (0x7, 0x1, EAX, 4),
+    AVX512_BF16 = (0x7, 0x1, EAX, 5),
+    LASS = (0x7, 0x1, EAX, 6),
+    CMPCCXADD = (0x7, 0x1, EAX, 7),
+    ARCH_PERFMON_EXT = (0x7, 0x1, EAX, 8),
+    FZRM = (0x7, 0x1, EAX, 10),
+    FSRS = (0x7, 0x1, EAX, 11),
+    FSRC = (0x7, 0x1, EAX, 12),
+    FRED = (0x7, 0x1, EAX, 17),
+    LKGS = (0x7, 0x1, EAX, 18),
+    WRMSRNS = (0x7, 0x1, EAX, 19),
+    AMX_FP16 = (0x7, 0x1, EAX, 21),
+    HRESET = (0x7, 0x1, EAX, 22),
+    AVX_IFMA = (0x7, 0x1, EAX, 23),
+    LAM = (0x7, 0x1, EAX, 26),
+    MSRLIST = (0x7, 0x1, EAX, 27),
+    /* 00_07_EDX_x1 */
+    AVX_VNNI_INT8 = (0x7, 0x1, EDX, 4),
+    AVX_NE_CONVERT = (0x7, 0x1, EDX, 5),
+    AMX_COMPLEX = (0x7, 0x1, EDX, 8),
+    AVX_VNNI_INT16 = (0x7, 0x1, EDX, 10),
+    PREFETCHI = (0x7, 0x1, EDX, 14),
+    USER_MSR = (0x7, 0x1, EDX, 15),
+    CET_SSS = (0x7, 0x1, EDX, 18),
+    AVX10 = (0x7, 0x1, EDX, 19),
+    APX_F = (0x7, 0x1, EDX, 21),
+    /* 00_07_EDX_x2 */
+    PSFD = (0x7, 0x2, EDX, 0),
+    IPRED_CTRL = (0x7, 0x2, EDX, 1),
+    RRSBA_CTRL = (0x7, 0x2, EDX, 2),
+    DDPD_U = (0x7, 0x2, EDX, 3),
+    BHI_CTRL = (0x7, 0x2, EDX, 4),
+    MCDT_NO = (0x7, 0x2, EDX, 5),
+    /* 00_0D_EAX_x1 */
+    XSAVEOPT = (0xD, 0x1, EAX, 0),
+    XSAVEC = (0xD, 0x1, EAX, 1),
+    XGETBV_ECX1 = (0xD, 0x1, EAX, 2),
+    XSAVES = (0xD, 0x1, EAX, 3),
+    XFD = (0xD, 0x1, EAX, 4),
+    /* 00_24_EBX */
+    AVX10_VL128 = (0x24, 0x0, EBX, 16),
+    AVX10_VL256 = (0x24, 0x0, EBX, 17),
+    AVX10_VL512 = (0x24, 0x0, EBX, 18),
+    /* 80_01_ECX */
+    LAHF_SAHF = (0x8000_0001, 0x0, ECX, 0),
+    CMP_LEGACY = (0x8000_0001, 0x0, ECX, 1),
+    SVM = (0x8000_0001, 0x0, ECX, 2),
+    EXT_APIC_SPACE = (0x8000_0001, 0x0, ECX, 3),
+    ALT_MOV_CR8 = (0x8000_0001, 0x0, ECX, 4),
+    ABM = (0x8000_0001, 0x0, ECX, 5),
+    SSE4A = (0x8000_0001, 0x0, ECX, 6),
+    MISALIGN_SSE = (0x8000_0001, 0x0, ECX, 7),
+    PREFETCHW = (0x8000_0001, 0x0, ECX, 8),
+    OSVW = (0x8000_0001, 0x0, ECX, 9),
+    IBS = (0x8000_0001, 0x0, ECX, 10),
+    XOP = (0x8000_0001, 0x0, ECX, 11),
+    SKINIT = (0x8000_0001, 0x0, ECX, 12),
+    WDT = (0x8000_0001, 0x0, ECX, 13),
+    LWP = (0x8000_0001, 0x0, ECX, 15),
+    FMA4 = (0x8000_0001, 0x0, ECX, 16),
+    TCE = (0x8000_0001, 0x0, ECX, 17),
+    TBM = (0x8000_0001, 0x0, ECX, 21),
+    TOPOEXT = (0x8000_0001, 0x0, ECX, 22),
+    PERFCTR_CORE = (0x8000_0001, 0x0, ECX, 23),
+    PERFCTR_NB = (0x8000_0001, 0x0, ECX, 24),
+    DATA_BKPT_EXT = (0x8000_0001, 0x0, ECX, 26),
+    PERF_TSC = (0x8000_0001, 0x0, ECX, 27),
+    PERFCTR_LLC = (0x8000_0001, 0x0, ECX, 28),
+    MONITORX = (0x8000_0001, 0x0, ECX, 29),
+    ADDR_MASK_EXT = (0x8000_0001, 0x0, ECX, 30),
+    /* 80_01_EDX */
+    SYSCALL = (0x8000_0001, 0x0, EDX, 11),
+    NX = (0x8000_0001, 0x0, EDX, 20),
+    MMXEXT = (0x8000_0001, 0x0, EDX, 22),
+    FFXSR = (0x8000_0001, 0x0, EDX, 25),
+    PAGE1GB = (0x8000_0001, 0x0, EDX, 26),
+    RDTSCP = (0x8000_0001, 0x0, EDX, 27),
+    LM = (0x8000_0001, 0x0, EDX, 29),
+    AMD3DNOW_EXT = (0x8000_0001, 0x0, EDX, 30),
+    AMD3DNOW = (0x8000_0001, 0x0, EDX, 31),
+    /* 80_07_EDX */
+    CPB = (0x8000_0007, 0x0, EDX, 9),
+    TSC_INVARIANT = (0x8000_0007, 0x0, EDX, 8),
+    RAPL = (0x8000_0007, 0x0, EDX, 14),
+    /* 80_08_EBX */
+    CLZERO = (0x8000_0008, 0x0, EBX, 0),
+    INST_RET_CNT_MSR = (0x8000_0008, 0x0, EBX, 1),
+    RSTR_FP_ERR_PTRS = (0x8000_0008, 0x0, EBX, 2),
+    INVLPGB = (0x8000_0008, 0x0, EBX, 3),
+    RDPRU = (0x8000_0008, 0x0, EBX, 4),
+    MCOMMIT = (0x8000_0008, 0x0, EBX, 8),
+    WBNOINVD = (0x8000_0008, 0x0, EBX, 9),
+    AMD_IBPB = (0x8000_0008, 0x0, EBX, 12),
+    INT_WBINVD = (0x8000_0008, 0x0, EBX, 13),
+    AMD_IBRS = (0x8000_0008, 0x0, EBX, 14),
+    AMD_STIBP = (0x8000_0008, 0x0, EBX, 15),
+    IBRS_ALWAYS_ON = (0x8000_0008, 0x0, EBX, 16),
+    STIBP_ALWAYS_ON = (0x8000_0008, 0x0, EBX, 17),
+    IBRS_PREFERRED = (0x8000_0008, 0x0, EBX, 18),
+    IBRS_SAME_MODE = (0x8000_0008, 0x0, EBX, 19),
+    EFER_LMSLE_UNSUPPORTED = (0x8000_0008, 0x0, EBX, 20),
+    INVLPGB_NESTED = (0x8000_0008, 0x0, EBX, 21),
+    PPIN = (0x8000_0008, 0x0, EBX, 23),
+    AMD_SSBD = (0x8000_0008, 0x0, EBX, 24),
+    VIRT_SSBD = (0x8000_0008, 0x0, EBX, 25),
+    SSB_NO = (0x8000_0008, 0x0, EBX, 26),
+    CPPC = (0x8000_0008, 0x0, EBX, 27),
+    AMD_PSFD = (0x8000_0008, 0x0, EBX, 28),
+    BTC_NO = (0x8000_0008, 0x0, EBX, 29),
+    IBPB_RET = (0x8000_0008, 0x0, EBX, 30),
+    /* 80_0A_EDX */
+    NPT = (0x8000_000A, 0x0, EDX, 0),
+    LBRV = (0x8000_000A, 0x0, EDX, 1),
+    NRIP_SAVE = (0x8000_000A, 0x0, EDX, 3),
+    AVIC = (0x8000_000A, 0x0, EDX, 13),
+    V_VMSAVE_VMLOAD = (0x8000_000A, 0x0, EDX, 15),
+    VGIF = (0x8000_000A, 0x0, EDX, 16),
+    X2AVIC = (0x8000_000A, 0x0, EDX, 18),
+    VNMI = (0x8000_000A, 0x0, EDX, 25),
+    /* 80_1F_EAX */
+    SME = (0x8000_001F, 0x0, EAX, 0),
+    SEV = (0x8000_001F, 0x0, EAX, 1),
+    SEV_ES = (0x8000_001F, 0x0, EAX, 3),
+    SEV_SNP = (0x8000_001F, 0x0, EAX, 4),
+    /* 80_21_EAX */
+    NO_NESTED_DATA_BP = (0x8000_0021, 0x0, EAX, 0),
+    LFENCE_RDTSC = (0x8000_0021, 0x0, EAX, 2),
+    NULL_SEL_CLR_BASE = (0x8000_0021, 0x0, EAX, 6),
+    AUTO_IBRS = (0x8000_0021, 0x0, EAX, 8),
+    NO_SMM_CTL_MSR = (0x8000_0021, 0x0, EAX, 9),
+    AMD_FSRS = (0x8000_0021, 0x0, EAX, 10),
+    AMD_FSRC = (0x8000_0021, 0x0, EAX, 11),
+    PREFETCH_CTL_MSR = (0x8000_0021, 0x0, EAX, 13),
+    SBPB = (0x8000_0021, 0x0, EAX, 27),
+    IBPB_BRTYPE = (0x8000_0021, 0x0, EAX, 28),
+    SRSO_NO = (0x8000_0021, 0x0, EAX, 29),
+    /* 80_22_EAX */
+    PERFMON_V2 = (0x8000_0022, 0x0, EAX, 0),
+    AMD_LBR_V2 = (0x8000_0022, 0x0, EAX, 1),
+    AMD_LBR_PMC_FREEZE = (0x8000_0022, 0x0, EAX, 2),
+}
+
+impl CpuFeature {
+    pub fn check(&self, cpuid: &CpuidResult) -> bool {
+        let loc = self.loc();
+
+        ((loc.reg.get(cpuid) >> loc.bit) & 0b1) == 0b1
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Error of parsing [CpuFeature] or [FeatureSet] from string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseCpuFeatureError;
+
+/* "avx512_vnni", "AVX512VNNI", "sse4.1", "AVX-VNNI" */
+fn eq_ftr_name(name: &str, s: &str) -> bool {
+    let mut s = s.chars()
+        .filter(|c| !matches!(c, '_' | '-' | '.' | '/' | ' '))
+        .map(|c| c.to_ascii_uppercase());
+
+    name.chars().filter(|c| *c != '_').all(|c| Some(c) == s.next()) && s.next().is_none()
+}
+
+impl FromStr for CpuFeature {
+    type Err = ParseCpuFeatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|ftr| eq_ftr_name(ftr.name(), s))
+            .copied()
+            .ok_or(ParseCpuFeatureError)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for CpuFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+const FEATURE_SET_LEN: usize = CpuFeature::ALL.len().div_ceil(64);
+
+/// Set of [CpuFeature]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FeatureSet {
+    bits: [u64; FEATURE_SET_LEN],
+}
+
+impl FeatureSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Supported features of the current thread
+    pub fn get() -> Self {
+        Self::from_source(&LiveCpuid)
+    }
+
+    /// Supported features from any [CpuidSource], e.g. [crate::Snapshot]
+    pub fn from_source<S: CpuidSource>(src: &S) -> Self {
+        /* Intel CPUs return the result of the highest basic leaf for unsupported leaves */
+        let max_std_leaf = src.cpuid(0x0, 0x0).eax;
+        let max_ext_leaf = src.cpuid(0x8000_0000, 0x0).eax;
+        let max_sub_leaf_07h = src.cpuid(0x7, 0x0).eax;
+
+        let mut set = Self::new();
+        let mut cache: Option<(u32, u32, CpuidResult)> = None;
+
+        for ftr in CpuFeature::ALL {
+            let CpuFeatureLoc { leaf, sub_leaf, .. } = ftr.loc();
+            let supported_leaf = if leaf < 0x8000_0000 {
+                leaf <= max_std_leaf && (leaf != 0x7 || sub_leaf <= max_sub_leaf_07h)
+            } else {
+                leaf <= max_ext_leaf
+            };
+
+            if !supported_leaf { continue }
+
+            /* CpuFeature::ALL is sorted by Leaf/Sub-leaf */
+            let cpuid = match cache {
+                Some((l, s, cpuid)) if l == leaf && s == sub_leaf => cpuid,
+                _ => {
+                    let cpuid = src.cpuid(leaf, sub_leaf);
+                    cache = Some((leaf, sub_leaf, cpuid));
+                    cpuid
+                },
+            };
+
+            if ftr.check(&cpuid) {
+                set.insert(*ftr);
+            }
+        }
+
+        set
+    }
+
+    pub fn has(&self, ftr: CpuFeature) -> bool {
+        let i = ftr.index();
+
+        ((self.bits[i / 64] >> (i % 64)) & 0b1) == 0b1
+    }
+
+    pub fn insert(&mut self, ftr: CpuFeature) {
+        let i = ftr.index();
+
+        self.bits[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn remove(&mut self, ftr: CpuFeature) {
+        let i = ftr.index();
+
+        self.bits[i / 64] &= !(1 << (i % 64));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = CpuFeature> + '_ {
+        CpuFeature::ALL.iter().copied().filter(move |ftr| self.has(*ftr))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|b| *b == 0)
+    }
+
+    fn zip_with(&self, other: &Self, f: fn(u64, u64) -> u64) -> Self {
+        let mut set = Self::new();
+
+        for (i, b) in set.bits.iter_mut().enumerate() {
+            *b = f(self.bits[i], other.bits[i]);
+        }
+
+        set
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// Features in `self` but not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+}
+
+impl BitOr for FeatureSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(&rhs)
+    }
+}
+
+impl BitAnd for FeatureSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(&rhs)
+    }
+}
+
+impl Sub for FeatureSet {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.difference(&rhs)
+    }
+}
+
+impl core::iter::FromIterator<CpuFeature> for FeatureSet {
+    fn from_iter<I: IntoIterator<Item = CpuFeature>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        for ftr in iter {
+            set.insert(ftr);
+        }
+
+        set
+    }
+}
+
+/// Comma or whitespace separated list of features, e.g. "avx2, avx512_vnni"
+impl FromStr for FeatureSet {
+    type Err = ParseCpuFeatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(|c: char| c == ',' || c.is_ascii_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(CpuFeature::from_str)
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for FeatureSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let list: Vec<&str> = self.iter().map(|ftr| ftr.name()).collect();
+
+        write!(f, "{}", list.join(" "))
+    }
+}
+
+#[test]
+fn test_feature_set() {
+    use crate::CpuidTable;
+
+    /* CPUID Ryzen 5 5600G */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let set = FeatureSet::from_source(&CpuidTable::from_raw_dump(dump));
+
+    assert!(set.has(CpuFeature::AVX2));
+    assert!(set.has(CpuFeature::SHA));
+    assert!(set.has(CpuFeature::CLZERO));
+    assert!(!set.has(CpuFeature::AVX512F));
+
+    assert_eq!("sse4.1".parse::<CpuFeature>(), Ok(CpuFeature::SSE4_1));
+    assert_eq!("avx512vnni".parse::<CpuFeature>(), Ok(CpuFeature::AVX512_VNNI));
+    assert_eq!("AVX-VNNI".parse::<CpuFeature>(), Ok(CpuFeature::AVX_VNNI));
+    assert!("AVX3".parse::<CpuFeature>().is_err());
+
+    let v3: FeatureSet = "avx, avx2 bmi1 bmi2 fma movbe".parse().unwrap();
+    assert_eq!(v3.len(), 6);
+    assert!(v3.is_subset(&set));
+
+    let avx512: FeatureSet = "avx512f avx512vl".parse().unwrap();
+    assert_eq!((v3 | avx512).len(), 8);
+    assert!((set & avx512).is_empty());
+    assert_eq!(avx512 - set, avx512);
+}
@@ -0,0 +1,100 @@
+use crate::{CpuidResult, CpuidSource};
+
+use std::fs::File;
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::path::Path;
+
+/// Read CPUID results of the specified CPU from the Linux cpuid device (`/dev/cpu/N/cpuid`),
+/// without pinning the thread.
+/// The device is provided by the `cpuid` kernel module (`CONFIG_X86_CPUID`), and requires read permission (usually root).
+#[derive(Debug)]
+pub struct CpuidDev {
+    file: File,
+    pub cpu: usize,
+}
+
+impl CpuidDev {
+    pub fn open(cpu: usize) -> io::Result<Self> {
+        Self::open_path(format!("/dev/cpu/{cpu}/cpuid"), cpu)
+    }
+
+    fn open_path<P: AsRef<Path>>(path: P, cpu: usize) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        Ok(Self { file, cpu })
+    }
+
+    /// The offset is `(sub_leaf << 32) | leaf`, and the result is EAX, EBX, ECX, EDX (16 bytes)
+    pub fn read(&self, leaf: u32, sub_leaf: u32) -> io::Result<CpuidResult> {
+        let mut buf = [0u8; 16];
+        let offset = ((sub_leaf as u64) << 32) | leaf as u64;
+
+        self.file.read_exact_at(&mut buf, offset)?;
+
+        let [eax, ebx, ecx, edx] = [0, 4, 8, 12].map(|i| {
+            u32::from_le_bytes([buf[i], buf[i+1], buf[i+2], buf[i+3]])
+        });
+
+        Ok(CpuidResult { eax, ebx, ecx, edx })
+    }
+
+    /// List of CPUs with the cpuid device, including CPUs outside the affinity mask of the process
+    pub fn cpu_list() -> io::Result<Vec<usize>> {
+        let mut cpus: Vec<usize> = std::fs::read_dir("/dev/cpu")?
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<usize>().ok())
+            .filter(|cpu| std::path::Path::new(&format!("/dev/cpu/{cpu}/cpuid")).exists())
+            .collect();
+
+        if cpus.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "cpuid device not found"));
+        }
+
+        cpus.sort_unstable();
+
+        Ok(cpus)
+    }
+}
+
+/// If reading the device fails, all zero is returned, same as an unsupported leaf.
+/// Use [`CpuidDev::read`] or [`crate::Snapshot::try_from_cpuid_dev`] to handle the error.
+impl CpuidSource for CpuidDev {
+    fn cpuid(&self, leaf: u32, sub_leaf: u32) -> CpuidResult {
+        self.read(leaf, sub_leaf).unwrap_or(CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 })
+    }
+}
+
+#[test]
+fn test_cpuid_dev_read() {
+    use crate::Snapshot;
+
+    /* Sparse file in place of the device, the offset is `(sub_leaf << 32) | leaf` */
+    let path = std::env::temp_dir().join(format!("cpuid_dev_test_{}", std::process::id()));
+    let file = File::create(&path).unwrap();
+    let write_result = |leaf: u32, sub_leaf: u32, regs: [u32; 4]| {
+        let buf: Vec<u8> = regs.iter().flat_map(|reg| reg.to_le_bytes()).collect();
+
+        file.write_all_at(&buf, ((sub_leaf as u64) << 32) | leaf as u64).unwrap();
+    };
+
+    write_result(0x0, 0x0, [0x10, 0x68747541, 0x444D4163, 0x69746E65]);
+    write_result(0x7, 0x1, [0x0, 0x0, 0x0, 0x00080000]);
+
+    let dev = CpuidDev::open_path(&path, 3).unwrap();
+
+    assert_eq!(dev.read(0x7, 0x1).unwrap().edx, 0x00080000);
+    assert!(dev.read(0x8000_0000, 0x1).is_err());
+    assert_eq!(dev.cpuid(0x8000_0000, 0x1), CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 });
+
+    let snapshot = Snapshot::try_from_cpuid_dev(&dev, &[(0x0, 0x0), (0x7, 0x1)], true).unwrap();
+
+    assert_eq!(snapshot.rawcpuid_pool.len(), 2);
+    assert_eq!(snapshot.thread_id, Some(3));
+
+    /* a short read fails the snapshot, not treated as an unsupported leaf */
+    let err = Snapshot::try_from_cpuid_dev(&dev, &[(0x0, 0x0), (0x8000_0000, 0x1)], true).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,149 @@
+use crate::CpuidResult;
+
+/// Source of CPUID results, such as the CPUID instruction or recorded data.
+pub trait CpuidSource {
+    fn cpuid(&self, leaf: u32, sub_leaf: u32) -> CpuidResult;
+}
+
+impl<S: CpuidSource + ?Sized> CpuidSource for &S {
+    fn cpuid(&self, leaf: u32, sub_leaf: u32) -> CpuidResult {
+        (**self).cpuid(leaf, sub_leaf)
+    }
+}
+
+/// Execute the CPUID instruction on the current thread
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LiveCpuid;
+
+impl CpuidSource for LiveCpuid {
+    fn cpuid(&self, leaf: u32, sub_leaf: u32) -> CpuidResult {
+        cpuid!(leaf, sub_leaf)
+    }
+}
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::iter::FromIterator;
+
+/// In-memory CPUID results, for recorded data (dump files, logs).
+/// Leaf/Sub-leaf not in the table return all zero, like an unsupported leaf on AMD CPUs.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuidTable {
+    table: BTreeMap<(u32, u32), CpuidResult>,
+}
+
+#[cfg(feature = "std")]
+impl CpuidTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, leaf: u32, sub_leaf: u32, result: CpuidResult) -> Option<CpuidResult> {
+        self.table.insert((leaf, sub_leaf), result)
+    }
+
+    pub fn get(&self, leaf: u32, sub_leaf: u32) -> Option<&CpuidResult> {
+        self.table.get(&(leaf, sub_leaf))
+    }
+
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32, &CpuidResult)> {
+        self.table.iter().map(|((leaf, sub_leaf), result)| (*leaf, *sub_leaf, result))
+    }
+
+    /// Parse a line of the raw/parse format of cpuid_dump:
+    /// `  0x80000008 0x0:  0x00003030 0x111EF657 0x0000500B 0x00000000  [...]`
+    pub fn parse_raw_line(line: &str) -> Option<(u32, u32, CpuidResult)> {
+        let hex = |s: &str| -> Option<u32> {
+            u32::from_str_radix(s.strip_prefix("0x")?, 16).ok()
+        };
+
+        let mut split = line.split_whitespace();
+        let leaf = hex(split.next()?)?;
+        let sub_leaf = hex(split.next()?.strip_suffix(':')?)?;
+        let [eax, ebx, ecx, edx] = [
+            hex(split.next()?)?,
+            hex(split.next()?)?,
+            hex(split.next()?)?,
+            hex(split.next()?)?,
+        ];
+
+        Some((leaf, sub_leaf, CpuidResult { eax, ebx, ecx, edx }))
+    }
+
+    /// Replay a dump file saved in the raw/parse format of cpuid_dump.
+    /// For dumps of all threads, the first result of each Leaf/Sub-leaf (first thread) is used.
+    pub fn from_raw_dump(dump: &str) -> Self {
+        let mut table = Self::new();
+
+        for (leaf, sub_leaf, result) in dump.lines().filter_map(Self::parse_raw_line) {
+            table.table.entry((leaf, sub_leaf)).or_insert(result);
+        }
+
+        table
+    }
+}
+
+#[cfg(feature = "std")]
+impl CpuidSource for CpuidTable {
+    fn cpuid(&self, leaf: u32, sub_leaf: u32) -> CpuidResult {
+        self.get(leaf, sub_leaf)
+            .copied()
+            .unwrap_or(CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 })
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromIterator<(u32, u32, CpuidResult)> for CpuidTable {
+    fn from_iter<I: IntoIterator<Item = (u32, u32, CpuidResult)>>(iter: I) -> Self {
+        Self {
+            table: iter.into_iter().map(|(leaf, sub_leaf, result)| ((leaf, sub_leaf), result)).collect(),
+        }
+    }
+}
+
+/// Table from `(Leaf, Sub-leaf, [EAX, EBX, ECX, EDX])`, for the test fixtures
+#[cfg(test)]
+impl CpuidTable {
+    pub(crate) fn from_regs(regs: &[(u32, u32, [u32; 4])]) -> Self {
+        regs.iter()
+            .map(|&(leaf, sub_leaf, [eax, ebx, ecx, edx])| (leaf, sub_leaf, CpuidResult { eax, ebx, ecx, edx }))
+            .collect()
+    }
+}
+
+#[test]
+fn test_cpuid_table_replay() {
+    use crate::*;
+
+    /* CPUID Ryzen 5 5600G, all threads */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let src = CpuidTable::from_raw_dump(dump);
+
+    assert_eq!(CpuVendor::get_from_source(&src), CpuVendor::AuthenticAMD);
+    assert_eq!(ProcName::get_trim_name_from_source(&src), "AMD Ryzen 5 5600G with Radeon Graphics");
+    assert_eq!(MicroArchLevel::check_from_source(&src), MicroArchLevel::X86_64_V3);
+    assert_eq!(util::get_threads_per_core_from_source(&src), Some(2));
+
+    let l3 = CacheProp::get_from_source(&src, 3, CacheType::Unified).unwrap();
+    assert_eq!(l3.size, 16 * Unit::MiB.to_byte());
+    assert_eq!(l3.share_thread, 12);
+
+    let topo_id = TopoId::get_topo_info_from_source(&src).unwrap();
+    assert_eq!(topo_id, TopoId { smt_id: 0, core_id: 0, pkg_id: 0, x2apic_id: 0 });
+
+    /* Leaf/Sub-leaf not in the table */
+    let src = CpuidTable::from_regs(&[(0x0, 0x0, [0x1, 0x756E6547, 0x6C65746E, 0x49656E69])]);
+
+    assert_eq!(CpuVendor::get_from_source(&src), CpuVendor::GenuineIntel);
+    assert_eq!(src.cpuid(0x1, 0x0), CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 });
+}
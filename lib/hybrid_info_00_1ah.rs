@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult};
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 
 /* https://github.com/slimbootloader/slimbootloader/blob/master/Platform/AlderlakeBoardPkg/Library/Stage2BoardInitLib/CpuInfoLib.c */
 
@@ -93,7 +93,14 @@ pub struct HybridInfo;
 
 impl HybridInfo {
     pub fn get_hybrid_info() -> (Option<HybridCoreType>, IntelNativeModelId) {
-        Self::get_hybrid_info_from_cpuid(&cpuid!(0x1A, 0x0))
+        Self::get_hybrid_info_from_source(&LiveCpuid)
+    }
+
+    pub fn get_hybrid_info_from_source<S: CpuidSource>(src: &S) -> (
+        Option<HybridCoreType>,
+        IntelNativeModelId
+    ) {
+        Self::get_hybrid_info_from_cpuid(&src.cpuid(0x1A, 0x0))
     }
 
     pub fn get_hybrid_info_from_cpuid(cpuid: &CpuidResult) -> (
@@ -1,7 +1,12 @@
 #[cfg(feature = "std")]
-use crate::{cpuid, CacheType, CacheProp, HybridCoreType, HybridInfo};
+use crate::{ArchPerfmon, CacheType, CacheProp, CpuidSource, HybridCoreType, HybridInfo, LiveCpuid};
 use crate::util::*;
 
+#[cfg(target_os = "linux")]
+use crate::CpuidDev;
+#[cfg(target_os = "linux")]
+use std::io;
+
 use std::sync::Arc;
 use std::thread;
 
@@ -30,7 +35,7 @@ impl TopoCacheInfo {
         let cache_leaf = Arc::new(CacheProp::get_cache_prop_leaf()?);
 
         if *cache_leaf == 0x8000_001D {
-            return Self::from_amd_80_1dh(*cache_leaf);
+            return Self::from_amd_80_1dh(&LiveCpuid, *cache_leaf);
         }
 
         let len = type_only_list.len();
@@ -184,14 +189,74 @@ impl TopoCacheInfo {
         })
     }
 
-    fn from_amd_80_1dh(cache_leaf: u32) -> Option<Self> {
+    /* same as `get_topology_cache_info`, from a source of each CPU instead of pinning the thread */
+    #[cfg(target_os = "linux")]
+    fn get_topology_cache_info_from_sources<S: CpuidSource>(srcs: &[S]) -> Option<Self> {
+        let first = srcs.first()?;
+        let cache_leaf = CacheProp::get_cache_prop_leaf_from_source(first)?;
+
+        if cache_leaf == 0x8000_001D {
+            return Self::from_amd_80_1dh(first, cache_leaf);
+        }
+
+        let max_apic_id = max_apic_id!(first.cpuid(0x1, 0x0).ebx);
+        /* L1d, L1i, L2, L3, L4 */
+        let mut caches: [Option<CachePropCount>; 5] = [None, None, None, None, None];
+        let mut ids: [Vec<u32>; 5] = Default::default();
+
+        for src in srcs {
+            let apicid = src.cpuid(0x1, 0x0).ebx >> 24;
+
+            for sub_leaf in 0x0..=0x4 {
+                let prop = match CacheProp::option_from_cpuid(&src.cpuid(cache_leaf, sub_leaf)) {
+                    Some(prop) => prop,
+                    None => continue,
+                };
+                let index = match prop {
+                    CacheProp { cache_type: CacheType::Data, level: 1, .. } => 0,
+                    CacheProp { cache_type: CacheType::Instruction, level: 1, .. } => 1,
+                    CacheProp { level: 2..=4, .. } => prop.level as usize,
+                    _ => continue,
+                };
+                let cache_id = Self::get_cache_id(apicid, prop.share_thread);
+
+                if !ids[index].contains(&cache_id) {
+                    ids[index].push(cache_id);
+                }
+
+                caches[index].get_or_insert_with(|| CachePropCount {
+                    shared_between_topology: Self::shared_all_threads(&prop, max_apic_id),
+                    prop,
+                    count: 0,
+                });
+            }
+        }
+
+        for (cache, ids) in caches.iter_mut().zip(ids) {
+            if let Some(cache) = cache {
+                cache.count = ids.len() as u32;
+            }
+        }
+
+        let [l1d, l1i, l2, l3, l4] = caches;
+
+        Some(Self {
+            l1d,
+            l1i,
+            l2,
+            l3,
+            l4,
+        })
+    }
+
+    fn from_amd_80_1dh<S: CpuidSource>(src: &S, cache_leaf: u32) -> Option<Self> {
         let [mut l1d, mut l1i, mut l2, mut l3, mut l4]: [Option<CachePropCount>; 5]
             = [None, None, None, None, None];
-        let total_logical_proc = get_total_logical_processor()?;
-        let max_apic_id = max_apic_id!();
+        let total_logical_proc = get_total_logical_processor_from_source(src)?;
+        let max_apic_id = max_apic_id!(src.cpuid(0x1, 0x0).ebx);
 
         for sub_leaf in 0x0..=0x4 {
-            let cpuid = cpuid!(cache_leaf, sub_leaf);
+            let cpuid = src.cpuid(cache_leaf, sub_leaf);
             let prop = match CacheProp::option_from_cpuid(&cpuid) {
                 Some(prop) => prop,
                 None => continue,
@@ -254,8 +319,8 @@ impl TopoCacheInfo {
     */
     /* Linux Kernel: arch/x86/kernel/cpu/cacheinfo.c */
     fn get_cache_id(apicid: u32, num_sharing_thread: u32) -> u32 {
-        /* find last set bit */
-        let index_msb = u32::BITS - num_sharing_thread.leading_zeros();
+        /* get_count_order: the smallest power of 2 >= the number of sharing threads */
+        let index_msb = u32::BITS - (num_sharing_thread - 1).leading_zeros();
 
         apicid & !((1 << index_msb) - 1)
     }
@@ -267,18 +332,24 @@ pub struct TopoPartInfo {
    pub num_logical_proc: u32,
    pub num_physical_proc: u32,
    pub cache: Option<TopoCacheInfo>,
+   pub perfmon: Option<ArchPerfmon>,
 }
 
 impl TopoPartInfo {
     pub fn check_hybrid_flag() -> bool {
-        let cpuid = (cpuid!(0x7, 0x0).edx >> 15) & 0b1;
+        Self::check_hybrid_flag_from_source(&LiveCpuid)
+    }
+
+    pub fn check_hybrid_flag_from_source<S: CpuidSource>(src: &S) -> bool {
+        let cpuid = (src.cpuid(0x7, 0x0).edx >> 15) & 0b1;
 
         cpuid == 0b1
     }
 
     fn get_core_type_only_list(core_type: HybridCoreType) -> Vec<usize> {
-        let core_type = Arc::new(core_type);
         let cpu_list = cpu_set_list().unwrap();
+
+        let core_type = Arc::new(core_type);
         let mut type_only_list: Vec<usize> = Vec::with_capacity(cpu_list.len());
         let mut handles: Vec<thread::JoinHandle<_>> = Vec::with_capacity(cpu_list.len());
 
@@ -309,12 +380,12 @@ impl TopoPartInfo {
     }
 
     pub fn get(core_type: HybridCoreType) -> Self {
-        let cpu_list = Self::get_core_type_only_list(core_type.clone());
+        let cpu_list = Self::get_core_type_only_list(core_type);
         /* core type only */
         let num_logical_proc = cpu_list.len() as u32;
 
         /* To confine the effects of pin_thread */
-        let (num_physical_proc, cache) = thread::scope(|s| s.spawn(move || {
+        let (num_physical_proc, cache, perfmon) = thread::scope(|s| s.spawn(move || {
             self::pin_thread(cpu_list[0]).unwrap();
 
             let threads_per_core = get_threads_per_core().unwrap_or(1);
@@ -322,6 +393,7 @@ impl TopoPartInfo {
             (
                 num_logical_proc / threads_per_core,
                 TopoCacheInfo::get_topology_cache_info(&cpu_list),
+                ArchPerfmon::get(),
             )
         }).join().unwrap());
 
@@ -330,6 +402,70 @@ impl TopoPartInfo {
             num_logical_proc,
             num_physical_proc,
             cache,
+            perfmon,
         }
     }
+
+    /// Read from the Linux cpuid device (`/dev/cpu/N/cpuid`) of the CPUs in [`CpuidDev::cpu_list`],
+    /// instead of pinning the thread to each CPU
+    #[cfg(target_os = "linux")]
+    pub fn from_cpuid_dev(core_type: HybridCoreType) -> io::Result<Self> {
+        let mut devs: Vec<CpuidDev> = Vec::new();
+
+        for cpu in CpuidDev::cpu_list()? {
+            let dev = CpuidDev::open(cpu)?;
+            /* the decoders below read through `CpuidSource`, which returns zeros on a read error */
+            let leaf_1ah = dev.read(0x1A, 0x0)?;
+
+            if HybridInfo::get_core_type(&leaf_1ah) == Some(core_type) {
+                devs.push(dev);
+            }
+        }
+
+        let first = devs.first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no CPU of {core_type:?}")))?;
+        /* core type only */
+        let num_logical_proc = devs.len() as u32;
+        let threads_per_core = get_threads_per_core_from_source(first).unwrap_or(1);
+
+        Ok(Self {
+            core_type,
+            num_logical_proc,
+            num_physical_proc: num_logical_proc / threads_per_core,
+            cache: TopoCacheInfo::get_topology_cache_info_from_sources(&devs),
+            perfmon: ArchPerfmon::get_from_source(first),
+        })
+    }
+}
+
+#[test]
+fn test_cache_id() {
+    let ids = |num_sharing_thread: u32| [0, 1, 2, 3, 12].map(|apicid| TopoCacheInfo::get_cache_id(apicid, num_sharing_thread));
+
+    assert_eq!(ids(1), [0, 1, 2, 3, 12]);
+    assert_eq!(ids(2), [0, 0, 2, 2, 12]);
+    assert_eq!(ids(12), [0, 0, 0, 0, 0]);
+    assert_eq!(ids(16), [0, 0, 0, 0, 0]);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_topology_cache_info_from_sources() {
+    use crate::CpuidTable;
+
+    /* 2 cores, L1/L2 shared by 2 threads, L3 shared by 16 threads */
+    let cpu = |apic_id: u32| CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x20, 0x756E6547, 0x6C65746E, 0x49656E69]),
+        (0x1, 0x0, [0x0, (apic_id << 24) | 0x00100800, 0x0, 0x0]),
+        (0x4, 0x0, [0x00004121, 0x02C0003F, 0x0000003F, 0x0]),
+        (0x4, 0x1, [0x00004122, 0x01C0003F, 0x0000003F, 0x0]),
+        (0x4, 0x2, [0x00004143, 0x0240003F, 0x000007FF, 0x0]),
+        (0x4, 0x3, [0x0003C163, 0x0240003F, 0x00007FFF, 0x4]),
+    ]);
+    let info = TopoCacheInfo::get_topology_cache_info_from_sources(&[cpu(0x0), cpu(0x1), cpu(0x2)]).unwrap();
+    let [l1d, l2, l3] = [info.l1d, info.l2, info.l3].map(Option::unwrap);
+
+    assert_eq!((l1d.count, l2.count, l3.count), (2, 2, 1));
+    assert!(!l2.shared_between_topology && l3.shared_between_topology);
+    assert!(info.l4.is_none());
 }
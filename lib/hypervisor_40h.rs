@@ -0,0 +1,414 @@
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
+
+/* Ref: https://github.com/torvalds/linux/blob/master/arch/x86/include/asm/kvm_para.h */
+/* Ref: https://github.com/torvalds/linux/blob/master/arch/x86/include/uapi/asm/kvm_para.h */
+/* Ref: https://github.com/torvalds/linux/blob/master/include/hyperv/hvgdk_mini.h */
+
+/// Hypervisor vendor, the signature of `CPUID.(EAX=4000_0000h + 100h * n)`: EBX, ECX, EDX
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HypervisorVendor {
+    /// "KVMKVMKVM\0\0\0"
+    Kvm,
+    /// "Microsoft Hv"
+    HyperV,
+    /// "XenVMMXenVMM"
+    Xen,
+    /// "VMwareVMware"
+    VMware,
+    /// "TCGTCGTCGTCG": QEMU without KVM
+    Tcg,
+    /// "ACRNACRNACRN"
+    Acrn,
+    /// "bhyve bhyve "
+    Bhyve,
+    Unknown([u8; 12]),
+}
+
+impl HypervisorVendor {
+    pub fn signature(cpuid: &CpuidResult) -> [u8; 12] {
+        let mut sig = [0u8; 12];
+
+        for (i, reg) in [cpuid.ebx, cpuid.ecx, cpuid.edx].iter().enumerate() {
+            sig[(i*4)..(i*4+4)].copy_from_slice(&reg.to_le_bytes());
+        }
+
+        sig
+    }
+
+    pub fn from_signature(sig: &[u8; 12]) -> Self {
+        match sig {
+            b"KVMKVMKVM\0\0\0" => Self::Kvm,
+            b"Microsoft Hv" => Self::HyperV,
+            b"XenVMMXenVMM" => Self::Xen,
+            b"VMwareVMware" => Self::VMware,
+            b"TCGTCGTCGTCG" => Self::Tcg,
+            b"ACRNACRNACRN" => Self::Acrn,
+            b"bhyve bhyve " => Self::Bhyve,
+            _ => Self::Unknown(*sig),
+        }
+    }
+}
+
+impl From<&CpuidResult> for HypervisorVendor {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self::from_signature(&Self::signature(cpuid))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for HypervisorVendor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Unknown(sig) => {
+                let sig: String = sig.iter().map(|&byte| char::from(byte)).filter(|c| !c.is_control()).collect();
+
+                write!(f, "Unknown({:?})", sig.trim_end())
+            },
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// Hypervisor leaf range, `CPUID.(EAX=4000_0000h + 100h * n)`.
+/// The hypervisor that emulates another hypervisor interface (e.g. KVM with Hyper-V enlightenments)
+/// puts the emulated one at 0x4000_0000 and its own at 0x4000_0100.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HypervisorInfo {
+    /// First leaf of the range
+    pub base: u32,
+    /// EAX: Largest leaf of the range
+    pub max_leaf: u32,
+    pub vendor: HypervisorVendor,
+}
+
+impl HypervisorInfo {
+    pub const BASE: u32 = 0x4000_0000;
+    pub const STRIDE: u32 = 0x100;
+    /// Linux scans the hypervisor signature up to this leaf (exclusive)
+    pub const END: u32 = 0x4001_0000;
+
+    /// Hypervisor Present: `CPUID.(EAX=01h):ECX[31]`,
+    /// the hypervisor leaves are not reserved for the hypervisor on bare metal
+    pub fn is_present<S: CpuidSource>(src: &S) -> bool {
+        ((src.cpuid(0x1, 0x0).ecx >> 31) & 0b1) != 0
+    }
+
+    /// `None` if the range at `base` has no known signature nor the valid largest leaf
+    pub fn from_source_at<S: CpuidSource>(src: &S, base: u32) -> Option<Self> {
+        let cpuid = src.cpuid(base, 0x0);
+        let vendor = HypervisorVendor::from(&cpuid);
+        let in_range = base <= cpuid.eax && cpuid.eax < base + Self::STRIDE;
+
+        let max_leaf = match vendor {
+            /* Old KVM reports 0 as the largest leaf */
+            HypervisorVendor::Kvm if cpuid.eax == 0 => base + 0x1,
+            HypervisorVendor::Unknown(sig) => {
+                let printable = sig[0] != 0 && sig.iter().all(|&b| b == 0 || b == b' ' || b.is_ascii_graphic());
+
+                if !in_range || !printable {
+                    return None;
+                }
+
+                cpuid.eax
+            },
+            _ if in_range => cpuid.eax,
+            _ => base,
+        };
+
+        Some(Self { base, max_leaf, vendor })
+    }
+
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// Hypervisor at 0x4000_0000, `None` on bare metal
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if !Self::is_present(src) {
+            return None;
+        }
+
+        Self::from_source_at(src, Self::BASE)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn list() -> Vec<Self> {
+        Self::list_from_source(&LiveCpuid)
+    }
+
+    /// All hypervisor leaf ranges in the stride of 0x100, empty on bare metal
+    #[cfg(feature = "std")]
+    pub fn list_from_source<S: CpuidSource>(src: &S) -> Vec<Self> {
+        if !Self::is_present(src) {
+            return Vec::new();
+        }
+
+        (Self::BASE..Self::END)
+            .step_by(Self::STRIDE as usize)
+            .filter_map(|base| Self::from_source_at(src, base))
+            .collect()
+    }
+
+    pub fn kvm_info<S: CpuidSource>(&self, src: &S) -> Option<KvmInfo> {
+        if self.vendor != HypervisorVendor::Kvm {
+            return None;
+        }
+
+        Some(KvmInfo::from(&src.cpuid(self.base + 0x1, 0x0)))
+    }
+
+    pub fn hyperv_info<S: CpuidSource>(&self, src: &S) -> Option<HyperVInfo> {
+        if self.vendor != HypervisorVendor::HyperV || self.max_leaf < self.base + 0x5 {
+            return None;
+        }
+
+        let [l1, l2, l3, l4, l5] = [0x1, 0x2, 0x3, 0x4, 0x5].map(|i| src.cpuid(self.base + i, 0x0));
+
+        Some(HyperVInfo::from_leaves(&l1, &l2, &l3, &l4, &l5))
+    }
+}
+
+/// KVM paravirtual features, the bit position of `CPUID.(EAX=4000_0001h):EAX`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KvmFeature {
+    ClockSource = 0,
+    NopIoDelay = 1,
+    MmuOp = 2,
+    ClockSource2 = 3,
+    AsyncPf = 4,
+    StealTime = 5,
+    PvEoi = 6,
+    PvUnhalt = 7,
+    PvTlbFlush = 9,
+    AsyncPfVmexit = 10,
+    PvSendIpi = 11,
+    PollControl = 12,
+    PvSchedYield = 13,
+    AsyncPfInt = 14,
+    MsiExtDestId = 15,
+    HcMapGpaRange = 16,
+    MigrationControl = 17,
+    ClockSourceStableBit = 24,
+}
+
+impl KvmFeature {
+    pub const ALL: [Self; 18] = [
+        Self::ClockSource,
+        Self::NopIoDelay,
+        Self::MmuOp,
+        Self::ClockSource2,
+        Self::AsyncPf,
+        Self::StealTime,
+        Self::PvEoi,
+        Self::PvUnhalt,
+        Self::PvTlbFlush,
+        Self::AsyncPfVmexit,
+        Self::PvSendIpi,
+        Self::PollControl,
+        Self::PvSchedYield,
+        Self::AsyncPfInt,
+        Self::MsiExtDestId,
+        Self::HcMapGpaRange,
+        Self::MigrationControl,
+        Self::ClockSourceStableBit,
+    ];
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for KvmFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// KVM paravirtual features, `CPUID.(EAX=4000_0001h)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KvmInfo {
+    /// EAX: Paravirtual features bitmap
+    pub features: u32,
+    /// EDX[0]: vCPUs are never preempted for an unlimited time, allowing optimizations
+    pub realtime_hint: bool,
+}
+
+impl From<&CpuidResult> for KvmInfo {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            features: cpuid.eax,
+            realtime_hint: (cpuid.edx & 0b1) != 0,
+        }
+    }
+}
+
+impl KvmInfo {
+    pub fn has_feature(&self, ftr: KvmFeature) -> bool {
+        ((self.features >> (ftr as u32)) & 0b1) != 0
+    }
+
+    pub fn available_features(&self) -> impl Iterator<Item = KvmFeature> + '_ {
+        IntoIterator::into_iter(KvmFeature::ALL).filter(move |ftr| self.has_feature(*ftr))
+    }
+}
+
+/// Hyper-V enlightenments (implementation recommendations), the bit position of `CPUID.(EAX=4000_0004h):EAX`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HyperVEnlightenment {
+    /// Use hypercall for address space switches rather than MOV to CR3
+    AddressSpaceSwitch = 0,
+    /// Use hypercall for local TLB flushes rather than INVLPG or MOV to CR3
+    LocalTlbFlush = 1,
+    /// Use hypercall for remote TLB flushes rather than inter-processor interrupts
+    RemoteTlbFlush = 2,
+    /// Use MSRs for accessing APIC registers EOI, ICR and TPR rather than their memory-mapped counterparts
+    ApicAccessMsr = 3,
+    /// Use the hypervisor-provided MSR to initiate a system RESET
+    SystemReset = 4,
+    /// Use relaxed timing in this partition
+    RelaxedTiming = 5,
+    /// Use a hypervisor-provided synthetic MSR for DMA remapping
+    DmaRemapping = 6,
+    InterruptRemapping = 7,
+    /// Use x2APIC MSRs
+    X2ApicMsr = 8,
+    /// Deprecating AutoEOI
+    DeprecateAutoEoi = 9,
+    SyntheticClusterIpi = 10,
+    ExProcessorMasks = 11,
+    /// Running in a nested partition
+    Nested = 12,
+    IntForMbecSyscalls = 13,
+    EnlightenedVmcs = 14,
+    SyncedTimeline = 15,
+    DirectLocalFlushEntire = 17,
+    NoNonArchCoreSharing = 18,
+}
+
+impl HyperVEnlightenment {
+    pub const ALL: [Self; 18] = [
+        Self::AddressSpaceSwitch,
+        Self::LocalTlbFlush,
+        Self::RemoteTlbFlush,
+        Self::ApicAccessMsr,
+        Self::SystemReset,
+        Self::RelaxedTiming,
+        Self::DmaRemapping,
+        Self::InterruptRemapping,
+        Self::X2ApicMsr,
+        Self::DeprecateAutoEoi,
+        Self::SyntheticClusterIpi,
+        Self::ExProcessorMasks,
+        Self::Nested,
+        Self::IntForMbecSyscalls,
+        Self::EnlightenedVmcs,
+        Self::SyncedTimeline,
+        Self::DirectLocalFlushEntire,
+        Self::NoNonArchCoreSharing,
+    ];
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for HyperVEnlightenment {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Hyper-V interface, `CPUID.(EAX=4000_0001h..=4000_0005h)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperVInfo {
+    /// 4000_0001h EAX: Hypervisor interface signature, "Hv#1" for the Hyper-V interface
+    pub interface: u32,
+    /// 4000_0002h EAX
+    pub build: u32,
+    /// 4000_0002h EBX[31:16]
+    pub major: u16,
+    /// 4000_0002h EBX[15:0]
+    pub minor: u16,
+    /// 4000_0003h EBX:EAX: Partition privilege mask
+    pub privileges: u64,
+    /// 4000_0003h EDX: Miscellaneous features
+    pub features: u32,
+    /// 4000_0004h EAX: Implementation recommendations
+    pub recommendations: u32,
+    /// 4000_0004h EBX: Recommended number of spinlock attempts, 0xFFFF_FFFF is never to notify
+    pub spinlock_retries: u32,
+    /// 4000_0005h EAX
+    pub max_vp: u32,
+    /// 4000_0005h EBX
+    pub max_lp: u32,
+}
+
+impl HyperVInfo {
+    /// "Hv#1"
+    pub const INTERFACE_HV1: u32 = 0x3123_7648;
+
+    pub fn from_leaves(
+        l1: &CpuidResult,
+        l2: &CpuidResult,
+        l3: &CpuidResult,
+        l4: &CpuidResult,
+        l5: &CpuidResult,
+    ) -> Self {
+        Self {
+            interface: l1.eax,
+            build: l2.eax,
+            major: (l2.ebx >> 16) as u16,
+            minor: (l2.ebx & 0xFFFF) as u16,
+            privileges: ((l3.ebx as u64) << 32) | l3.eax as u64,
+            features: l3.edx,
+            recommendations: l4.eax,
+            spinlock_retries: l4.ebx,
+            max_vp: l5.eax,
+            max_lp: l5.ebx,
+        }
+    }
+
+    pub fn is_enlightened(&self, e: HyperVEnlightenment) -> bool {
+        ((self.recommendations >> (e as u32)) & 0b1) != 0
+    }
+
+    pub fn enlightenments(&self) -> impl Iterator<Item = HyperVEnlightenment> + '_ {
+        IntoIterator::into_iter(HyperVEnlightenment::ALL).filter(move |e| self.is_enlightened(*e))
+    }
+}
+
+#[test]
+fn test_hypervisor_info() {
+    use crate::CpuidTable;
+
+    /* KVM with Hyper-V enlightenments */
+    let src = CpuidTable::from_regs(&[
+        (0x1, 0x0, [0x806F8, 0x0, 0x80000000, 0x0]),
+        (0x4000_0000, 0x0, [0x4000000B, 0x7263694D, 0x666F736F, 0x76482074]),
+        (0x4000_0001, 0x0, [0x31237648, 0x0, 0x0, 0x0]),
+        (0x4000_0002, 0x0, [0x00003839, 0x000A0000, 0x0, 0x0]),
+        (0x4000_0003, 0x0, [0x00003A7F, 0x0, 0x0, 0x00000508]),
+        (0x4000_0004, 0x0, [0x00040E24, 0xFFFFFFFF, 0x0, 0x0]),
+        (0x4000_0005, 0x0, [0x000003FF, 0x0, 0x0, 0x0]),
+        (0x4000_0100, 0x0, [0x40000101, 0x4B4D564B, 0x564B4D56, 0x0000004D]),
+        (0x4000_0101, 0x0, [0x01007AFB, 0x0, 0x0, 0x0]),
+    ]);
+
+    let list = HypervisorInfo::list_from_source(&src);
+    let vendors: Vec<HypervisorVendor> = list.iter().map(|hv| hv.vendor).collect();
+
+    assert_eq!(vendors, [HypervisorVendor::HyperV, HypervisorVendor::Kvm]);
+    assert_eq!((list[0].max_leaf, list[1].base, list[1].max_leaf), (0x4000_000B, 0x4000_0100, 0x4000_0101));
+
+    let hyperv = list[0].hyperv_info(&src).unwrap();
+
+    assert_eq!(hyperv.interface, HyperVInfo::INTERFACE_HV1);
+    assert_eq!((hyperv.major, hyperv.minor), (10, 0));
+    assert!(hyperv.is_enlightened(HyperVEnlightenment::RelaxedTiming));
+    assert!(!hyperv.is_enlightened(HyperVEnlightenment::EnlightenedVmcs));
+
+    let kvm = list[1].kvm_info(&src).unwrap();
+
+    assert!(kvm.has_feature(KvmFeature::PvUnhalt) && kvm.has_feature(KvmFeature::ClockSourceStableBit));
+    assert!(!kvm.has_feature(KvmFeature::MmuOp));
+
+    /* CPUID Ryzen 5 5600G, bare metal */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let src = CpuidTable::from_raw_dump(dump);
+
+    assert!(HypervisorInfo::list_from_source(&src).is_empty());
+}
@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult};
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 
 /// Information available from `CPUID.(EAX=01h):EBX`
 #[derive(Debug, Clone)]
@@ -28,6 +28,10 @@ impl From<&CpuidResult> for Info01h {
 
 impl Info01h {
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x1, 0x0))
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x1, 0x0))
     }
 }
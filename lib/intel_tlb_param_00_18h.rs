@@ -1,4 +1,6 @@
 use crate::CpuidResult;
+#[cfg(feature = "std")]
+use crate::{CpuidSource, LiveCpuid};
 
 #[derive(Debug, Clone)]
 pub struct IntelTlbParam {
@@ -48,11 +50,16 @@ impl From<&CpuidResult> for IntelTlbParam {
 impl IntelTlbParam {
     #[cfg(feature = "std")]
     pub fn get() -> Vec<Self> {
-        let max_sub_leaf = cpuid!(0x18, 0).eax;
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Vec<Self> {
+        let max_sub_leaf = src.cpuid(0x18, 0).eax;
         let mut params: Vec<Self> = Vec::new();
 
         for sub_leaf in 0..=max_sub_leaf {
-            let cpuid = cpuid!(0x18, sub_leaf);
+            let cpuid = src.cpuid(0x18, sub_leaf);
             params.push(Self::from(&cpuid));
         }
 
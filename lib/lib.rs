@@ -8,13 +8,24 @@ use core::arch::x86_64::CpuidResult;
 #[macro_export]
 macro_rules! cpuid {
     ($leaf: expr) => {
-        unsafe { core::arch::x86_64::__cpuid_count($leaf, 0x0) }
-    };
-    ($leaf: expr, $sub_leaf: expr) => {
-        unsafe { core::arch::x86_64::__cpuid_count($leaf, $sub_leaf) }
+        $crate::cpuid!($leaf, 0x0)
     };
+    ($leaf: expr, $sub_leaf: expr) => {{
+        let (leaf, sub_leaf): (u32, u32) = ($leaf, $sub_leaf);
+        /* `__cpuid_count` is a safe function in newer Rust */
+        #[allow(unused_unsafe)]
+        let cpuid = unsafe { core::arch::x86_64::__cpuid_count(leaf, sub_leaf) };
+
+        cpuid
+    }};
 }
 
+mod cpuid_source;
+pub use cpuid_source::*;
+
+mod raw_cpuid;
+pub use raw_cpuid::*;
+
 #[macro_use]
 pub mod util;
 // pub use util::*;
@@ -28,6 +39,9 @@ pub use vendor::*;
 mod micro_arch_level;
 pub use micro_arch_level::*;
 
+mod cpu_feature;
+pub use cpu_feature::*;
+
 #[cfg(feature = "std")]
 mod proc_name;
 #[cfg(feature = "std")]
@@ -36,24 +50,51 @@ pub use proc_name::*;
 mod cache_prop;
 pub use cache_prop::*;
 
+mod cache_desc_00_02h;
+pub use cache_desc_00_02h::*;
+
 mod info_01h_ebx;
 pub use info_01h_ebx::*;
 
 mod monitor_mwait_00_05h;
 pub use monitor_mwait_00_05h::*;
 
+mod xsave_00_0dh;
+pub use xsave_00_0dh::*;
+
 mod intel_ext_topo_0bh_1fh;
 pub use intel_ext_topo_0bh_1fh::*;
 
 mod amd_tlb_info;
 pub use amd_tlb_info::*;
 
+mod rdt_00_0fh_10h;
+pub use rdt_00_0fh_10h::*;
+
+mod tsc_freq_00_15h;
+pub use tsc_freq_00_15h::*;
+
+mod sgx_00_12h;
+pub use sgx_00_12h::*;
+
+mod proc_trace_00_14h;
+pub use proc_trace_00_14h::*;
+
 mod intel_tlb_param_00_18h;
 pub use intel_tlb_param_00_18h::*;
 
 mod hybrid_info_00_1ah;
 pub use hybrid_info_00_1ah::*;
 
+mod amx_1dh_1eh;
+pub use amx_1dh_1eh::*;
+
+mod avx10_24h;
+pub use avx10_24h::*;
+
+mod arch_perfmon_0ah_23h;
+pub use arch_perfmon_0ah_23h::*;
+
 mod topo_info;
 pub use topo_info::*;
 
@@ -62,6 +103,12 @@ mod hybrid_topology;
 #[cfg(feature = "std")]
 pub use hybrid_topology::*;
 
+mod hypervisor_40h;
+pub use hypervisor_40h::*;
+
+mod confidential_computing;
+pub use confidential_computing::*;
+
 mod addr_size_80_08h_eax;
 pub use addr_size_80_08h_eax::*;
 
@@ -74,5 +121,24 @@ pub use amd_pkg_type_80_01h::*;
 mod amd_proc_topo_80_1eh;
 pub use amd_proc_topo_80_1eh::*;
 
+mod amd_mem_encrypt_80_1fh;
+pub use amd_mem_encrypt_80_1fh::*;
+
+mod amd_ext_ftr_80_21h;
+pub use amd_ext_ftr_80_21h::*;
+
+mod amd_perfmon_80_22h;
+pub use amd_perfmon_80_22h::*;
+
 mod amd_ext_topo_80_26h;
 pub use amd_ext_topo_80_26h::*;
+
+#[cfg(feature = "std")]
+mod snapshot;
+#[cfg(feature = "std")]
+pub use snapshot::*;
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+mod cpuid_dev;
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub use cpuid_dev::*;
@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult};
+use crate::{Avx10Info, CpuidResult, CpuidSource, LiveCpuid};
 
 /// Micro-architecture level defined by the x86-64 psABI
 #[allow(non_camel_case_types)]
@@ -78,11 +78,11 @@ impl MicroArchLevel {
         AVX512F | AVX512DQ | AVX512CD | AVX512BW | AVX512VL 
     };
     
-    fn set_cpuid() -> [CpuidResult; 3] {
+    fn set_cpuid<S: CpuidSource>(src: &S) -> [CpuidResult; 3] {
         [
-            cpuid!(0x1, 0x0),
-            cpuid!(0x7, 0x0),
-            cpuid!(0x8000_0001, 0x0),
+            src.cpuid(0x1, 0x0),
+            src.cpuid(0x7, 0x0),
+            src.cpuid(0x8000_0001, 0x0),
         ]
     }
 
@@ -125,9 +125,19 @@ impl MicroArchLevel {
         }
     }
     pub fn check() -> Self {
-        let cpuid_array = Self::set_cpuid();
+        Self::check_from_source(&LiveCpuid)
+    }
+
+    pub fn check_from_source<S: CpuidSource>(src: &S) -> Self {
+        let cpuid_array = Self::set_cpuid(src);
+        let level = Self::from_cpuid_array(cpuid_array);
+
+        /* AVX10.x/512 includes AVX512F, AVX512CD, AVX512BW, AVX512DQ and AVX512VL */
+        if level == Self::X86_64_V3 && Avx10Info::get_from_source(src).is_some_and(|avx10| avx10.vl512) {
+            return Self::X86_64_V4;
+        }
 
-        Self::from_cpuid_array(cpuid_array)
+        level
     }
 }
 
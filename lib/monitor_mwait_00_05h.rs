@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult};
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 
 /// Information available from `CPUID.(EAX=05h)`
 #[derive(Debug, Clone)]
@@ -40,6 +40,10 @@ impl From<&CpuidResult> for MonitorMwait {
 
 impl MonitorMwait {
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x5, 0x0))
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x5, 0x0))
     }
 }
@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult};
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 
 /// Processor name
 pub struct ProcName;
@@ -27,11 +27,11 @@ impl ProcName {
         total
     }
 
-    fn set_cpuid() -> [CpuidResult; 3] {
+    fn set_cpuid<S: CpuidSource>(src: &S) -> [CpuidResult; 3] {
         [
-            cpuid!(0x8000_0002, 0x0),
-            cpuid!(0x8000_0003, 0x0),
-            cpuid!(0x8000_0004, 0x0),
+            src.cpuid(0x8000_0002, 0x0),
+            src.cpuid(0x8000_0003, 0x0),
+            src.cpuid(0x8000_0004, 0x0),
         ]
     }
     
@@ -43,13 +43,21 @@ impl ProcName {
     }
     
     pub fn get_name() -> String {
-        let cpuid = Self::set_cpuid();
+        Self::get_name_from_source(&LiveCpuid)
+    }
+
+    pub fn get_name_from_source<S: CpuidSource>(src: &S) -> String {
+        let cpuid = Self::set_cpuid(src);
 
         Self::from_cpuid_array(cpuid)
     }
     
     pub fn get_trim_name() -> String {
-        Self::get_name()
+        Self::get_trim_name_from_source(&LiveCpuid)
+    }
+
+    pub fn get_trim_name_from_source<S: CpuidSource>(src: &S) -> String {
+        Self::get_name_from_source(src)
             .trim()
             .to_string()
     }
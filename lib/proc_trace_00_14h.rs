@@ -0,0 +1,122 @@
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
+
+/// Intel Processor Trace capability, `CPUID.(EAX=14h)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessorTraceCaps {
+    /// Sub-leaf 0, EAX: Maximum valid sub-leaf
+    pub max_sub_leaf: u32,
+    /// Sub-leaf 0, EBX[0]: IA32_RTIT_CTL.CR3Filter can be set to 1
+    pub cr3_filter: bool,
+    /// Sub-leaf 0, EBX[1]: Configurable PSB and Cycle-Accurate Mode
+    pub psb_cyc: bool,
+    /// Sub-leaf 0, EBX[2]: IP Filtering, TraceStop filtering, and preservation of MSRs across warm reset
+    pub ip_filter: bool,
+    /// Sub-leaf 0, EBX[3]: MTC timing packet and suppression of COFI-based packets
+    pub mtc: bool,
+    /// Sub-leaf 0, EBX[4]: PTWRITE
+    pub ptwrite: bool,
+    /// Sub-leaf 0, EBX[5]: Power Event Trace
+    pub power_event_trace: bool,
+    /// Sub-leaf 0, EBX[6]: PSB and PMI preservation
+    pub psb_pmi_preserve: bool,
+    /// Sub-leaf 0, EBX[7]: Event Trace packet generation through IA32_RTIT_CTL.EventEn
+    pub event_trace: bool,
+    /// Sub-leaf 0, EBX[8]: TNT packet generation disable through IA32_RTIT_CTL.DisTNT
+    pub tnt_disable: bool,
+    /// Sub-leaf 0, ECX[0]: ToPA (Table of Physical Addresses) output
+    pub topa: bool,
+    /// Sub-leaf 0, ECX[1]: ToPA tables can hold any number of output entries
+    pub topa_multi_entry: bool,
+    /// Sub-leaf 0, ECX[2]: Single-Range Output scheme
+    pub single_range_output: bool,
+    /// Sub-leaf 0, ECX[3]: Output to Trace Transport subsystem
+    pub trace_transport: bool,
+    /// Sub-leaf 0, ECX[31]: IP payloads have LIP values, which include the CS base component
+    pub lip: bool,
+    /// Sub-leaf 1, EAX[2:0]: Number of configurable address ranges for filtering
+    pub num_addr_ranges: u8,
+    /// Sub-leaf 1, EAX[31:16]: Bitmap of supported MTC period encodings
+    pub mtc_period_bitmap: u16,
+    /// Sub-leaf 1, EBX[15:0]: Bitmap of supported Cycle Threshold value encodings
+    pub cyc_threshold_bitmap: u16,
+    /// Sub-leaf 1, EBX[31:16]: Bitmap of supported Configurable PSB frequency encodings
+    pub psb_freq_bitmap: u16,
+}
+
+impl ProcessorTraceCaps {
+    /// `sub1` is ignored if the maximum sub-leaf is 0
+    pub fn from_sub_leaf_0_1(sub0: &CpuidResult, sub1: &CpuidResult) -> Self {
+        let ebx = |pos: u32| ((sub0.ebx >> pos) & 0b1) != 0;
+        let ecx = |pos: u32| ((sub0.ecx >> pos) & 0b1) != 0;
+        let [sub1_eax, sub1_ebx] = if sub0.eax != 0 { [sub1.eax, sub1.ebx] } else { [0, 0] };
+
+        Self {
+            max_sub_leaf: sub0.eax,
+            cr3_filter: ebx(0),
+            psb_cyc: ebx(1),
+            ip_filter: ebx(2),
+            mtc: ebx(3),
+            ptwrite: ebx(4),
+            power_event_trace: ebx(5),
+            psb_pmi_preserve: ebx(6),
+            event_trace: ebx(7),
+            tnt_disable: ebx(8),
+            topa: ecx(0),
+            topa_multi_entry: ecx(1),
+            single_range_output: ecx(2),
+            trace_transport: ecx(3),
+            lip: ecx(31),
+            num_addr_ranges: (sub1_eax & 0b111) as u8,
+            mtc_period_bitmap: (sub1_eax >> 16) as u16,
+            cyc_threshold_bitmap: (sub1_ebx & 0xFFFF) as u16,
+            psb_freq_bitmap: (sub1_ebx >> 16) as u16,
+        }
+    }
+
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if Processor Trace (`CPUID.(EAX=07h, ECX=0):EBX[25]`) is not supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if src.cpuid(0x0, 0x0).eax < 0x14 || ((src.cpuid(0x7, 0x0).ebx >> 25) & 0b1) == 0 {
+            return None;
+        }
+
+        Some(Self::from_sub_leaf_0_1(&src.cpuid(0x14, 0x0), &src.cpuid(0x14, 0x1)))
+    }
+
+    /// MTC frequency encodings (IA32_RTIT_CTL.MTCFreq) that can be used, `None` if MTC is not supported
+    pub fn mtc_periods(&self) -> Option<impl Iterator<Item = u8> + '_> {
+        self.mtc.then(|| (0..16u8).filter(move |i| (self.mtc_period_bitmap >> i) & 0b1 != 0))
+    }
+
+    /// Cycle threshold encodings (IA32_RTIT_CTL.CycThresh) that can be used, `None` if CYC is not supported
+    pub fn cyc_thresholds(&self) -> Option<impl Iterator<Item = u8> + '_> {
+        self.psb_cyc.then(|| (0..16u8).filter(move |i| (self.cyc_threshold_bitmap >> i) & 0b1 != 0))
+    }
+
+    /// PSB frequency encodings (IA32_RTIT_CTL.PSBFreq) that can be used, `None` if not configurable
+    pub fn psb_freqs(&self) -> Option<impl Iterator<Item = u8> + '_> {
+        self.psb_cyc.then(|| (0..16u8).filter(move |i| (self.psb_freq_bitmap >> i) & 0b1 != 0))
+    }
+}
+
+#[test]
+fn test_proc_trace_caps() {
+    let sub0 = CpuidResult { eax: 0x1, ebx: 0x5F, ecx: 0x80000007, edx: 0x0 };
+    let sub1 = CpuidResult { eax: 0x02490002, ebx: 0x003F3FFF, ecx: 0x0, edx: 0x0 };
+    let pt = ProcessorTraceCaps::from_sub_leaf_0_1(&sub0, &sub1);
+
+    assert!(pt.cr3_filter && pt.psb_cyc && pt.ip_filter && pt.mtc && pt.ptwrite);
+    assert!(!pt.power_event_trace && pt.psb_pmi_preserve && !pt.event_trace);
+    assert!(pt.topa && pt.topa_multi_entry && pt.single_range_output && !pt.trace_transport && pt.lip);
+    assert_eq!(pt.num_addr_ranges, 2);
+    assert_eq!(pt.mtc_periods().unwrap().collect::<Vec<u8>>(), [0, 3, 6, 9]);
+    assert_eq!(pt.cyc_thresholds().unwrap().count(), 14);
+    assert_eq!(pt.psb_freqs().unwrap().collect::<Vec<u8>>(), [0, 1, 2, 3, 4, 5]);
+
+    let pt = ProcessorTraceCaps::from_sub_leaf_0_1(&CpuidResult { eax: 0x0, ..sub0 }, &sub1);
+
+    assert_eq!((pt.num_addr_ranges, pt.mtc_period_bitmap), (0, 0));
+}
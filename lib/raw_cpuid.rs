@@ -0,0 +1,27 @@
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
+
+/// CPUID result with the input Leaf/Sub-leaf
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawCpuid {
+    pub leaf: u32,
+    pub sub_leaf: u32,
+    pub result: CpuidResult,
+}
+
+impl RawCpuid {
+    pub fn exe(leaf: u32, sub_leaf: u32) -> Self {
+        Self::exe_from_source(&LiveCpuid, leaf, sub_leaf)
+    }
+
+    pub fn exe_from_source<S: CpuidSource>(src: &S, leaf: u32, sub_leaf: u32) -> Self {
+        Self {
+            leaf,
+            sub_leaf,
+            result: src.cpuid(leaf, sub_leaf),
+        }
+    }
+
+    pub fn check_result_zero(&self) -> bool {
+        self.result == CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 }
+    }
+}
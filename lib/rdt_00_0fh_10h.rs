@@ -0,0 +1,249 @@
+use crate::{CpuidResult, CpuidSource, CpuVendor, LiveCpuid};
+
+/// L3 cache monitoring, `CPUID.(EAX=0Fh, ECX=1)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdtL3Monitoring {
+    /// ECX: Maximum range (zero-based) of RMID of L3
+    pub max_rmid: u32,
+    /// EBX: Conversion factor from the reported IA32_QM_CTR value to the occupancy metric (bytes) and MBM
+    pub upscaling_factor: u32,
+    /// EAX[7:0]: Counter width is 24 + the offset
+    pub counter_width: u8,
+    /// EAX[8]: Overflow bit in IA32_QM_CTR
+    pub overflow_bit: bool,
+    /// EDX[0]
+    pub occupancy: bool,
+    /// EDX[1]
+    pub total_mbm: bool,
+    /// EDX[2]
+    pub local_mbm: bool,
+}
+
+impl From<&CpuidResult> for RdtL3Monitoring {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            max_rmid: cpuid.ecx,
+            upscaling_factor: cpuid.ebx,
+            counter_width: 24 + (cpuid.eax & 0xFF) as u8,
+            overflow_bit: ((cpuid.eax >> 8) & 0b1) != 0,
+            occupancy: (cpuid.edx & 0b1) != 0,
+            total_mbm: (cpuid.edx & 0b10) != 0,
+            local_mbm: (cpuid.edx & 0b100) != 0,
+        }
+    }
+}
+
+/// Bandwidth Monitoring Event Configuration, `CPUID.(EAX=8000_0020h, ECX=3)`, AMD CPU only
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmdBmec {
+    /// EBX[7:0]: Number of configurable bandwidth events
+    pub num_events: u8,
+    /// ECX: Bandwidth types that can be monitored
+    pub valid_events: u32,
+}
+
+impl From<&CpuidResult> for AmdBmec {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            num_events: (cpuid.ebx & 0xFF) as u8,
+            valid_events: cpuid.ecx,
+        }
+    }
+}
+
+/// Resource Director Technology Monitoring (Intel RDT-M, AMD PQM), `CPUID.(EAX=0Fh)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdtMonitoring {
+    /// Sub-leaf 0, EBX: Maximum range (zero-based) of RMID of all resource types
+    pub max_rmid: u32,
+    pub l3: Option<RdtL3Monitoring>,
+    pub amd_bmec: Option<AmdBmec>,
+}
+
+impl RdtMonitoring {
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if PQM (`CPUID.(EAX=07h, ECX=0):EBX[12]`) is not supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if src.cpuid(0x0, 0x0).eax < 0xF || ((src.cpuid(0x7, 0x0).ebx >> 12) & 0b1) == 0 {
+            return None;
+        }
+
+        let sub0 = src.cpuid(0xF, 0x0);
+        let l3 = if ((sub0.edx >> 1) & 0b1) != 0 {
+            Some(RdtL3Monitoring::from(&src.cpuid(0xF, 0x1)))
+        } else {
+            None
+        };
+        let amd_bmec = AmdPqe::ebx_bitmap(src)
+            .filter(|ebx| ((ebx >> 3) & 0b1) != 0)
+            .map(|_| AmdBmec::from(&src.cpuid(0x8000_0020, 0x3)));
+
+        Some(Self {
+            max_rmid: sub0.ebx,
+            l3,
+            amd_bmec,
+        })
+    }
+}
+
+/// Cache Allocation Technology, `CPUID.(EAX=10h, ECX=1)` (L3) and `CPUID.(EAX=10h, ECX=2)` (L2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdtCat {
+    /// EAX[4:0] + 1: Length of the capacity bitmask
+    pub cbm_len: u8,
+    /// EBX: Bitmask of the shareable resource with other executing entities (e.g. I/O)
+    pub shareable_bitmap: u32,
+    /// ECX[2]: Code and Data Prioritization
+    pub cdp: bool,
+    /// ECX[3]: Non-contiguous capacity bitmask supported
+    pub non_contiguous_cbm: bool,
+    /// EDX[15:0]: Highest COS number supported
+    pub max_cos: u16,
+}
+
+impl From<&CpuidResult> for RdtCat {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            cbm_len: (cpuid.eax & 0x1F) as u8 + 1,
+            shareable_bitmap: cpuid.ebx,
+            cdp: ((cpuid.ecx >> 2) & 0b1) != 0,
+            non_contiguous_cbm: ((cpuid.ecx >> 3) & 0b1) != 0,
+            max_cos: (cpuid.edx & 0xFFFF) as u16,
+        }
+    }
+}
+
+/// Memory Bandwidth Allocation, `CPUID.(EAX=10h, ECX=3)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdtMba {
+    /// EAX[11:0] + 1: Maximum MBA throttling value
+    pub max_throttling: u16,
+    /// ECX[2]: Response of the delay values is linear
+    pub linear: bool,
+    /// EDX[15:0]: Highest COS number supported
+    pub max_cos: u16,
+}
+
+impl From<&CpuidResult> for RdtMba {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            max_throttling: (cpuid.eax & 0xFFF) as u16 + 1,
+            linear: ((cpuid.ecx >> 2) & 0b1) != 0,
+            max_cos: (cpuid.edx & 0xFFFF) as u16,
+        }
+    }
+}
+
+/// Memory Bandwidth Enforcement, `CPUID.(EAX=8000_0020h, ECX=1)` (L3MBE) and `ECX=2` (L3SMBE), AMD CPU only
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmdBwEnforce {
+    /// EAX: Size of the bandwidth specifier field in the L3QOS_BW_CONTROL_n MSRs
+    pub bw_len: u32,
+    /// EDX: Highest COS number supported
+    pub max_cos: u32,
+}
+
+impl From<&CpuidResult> for AmdBwEnforce {
+    fn from(cpuid: &CpuidResult) -> Self {
+        Self {
+            bw_len: cpuid.eax,
+            max_cos: cpuid.edx,
+        }
+    }
+}
+
+/// AMD Platform QoS Enforcement for Memory Bandwidth, `CPUID.(EAX=8000_0020h)`
+pub struct AmdPqe;
+
+impl AmdPqe {
+    /// Sub-leaf 0, EBX: bit 1: L3MBE, bit 2: L3SMBE, bit 3: BMEC, bit 4: L3RR, bit 5: ABMC, bit 6: SDCIAE
+    pub fn ebx_bitmap<S: CpuidSource>(src: &S) -> Option<u32> {
+        if CpuVendor::get_from_source(src) != CpuVendor::AuthenticAMD
+        || src.cpuid(0x8000_0000, 0x0).eax < 0x8000_0020 {
+            return None;
+        }
+
+        Some(src.cpuid(0x8000_0020, 0x0).ebx)
+    }
+}
+
+/// Resource Director Technology Allocation (Intel RDT-A, AMD PQE), `CPUID.(EAX=10h)` and AMD `CPUID.(EAX=8000_0020h)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdtAllocation {
+    pub l3_cat: Option<RdtCat>,
+    pub l2_cat: Option<RdtCat>,
+    pub mba: Option<RdtMba>,
+    pub amd_l3_mbe: Option<AmdBwEnforce>,
+    pub amd_l3_smbe: Option<AmdBwEnforce>,
+}
+
+impl RdtAllocation {
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if neither PQE (`CPUID.(EAX=07h, ECX=0):EBX[15]`) nor the AMD memory bandwidth enforcement is supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        let pqe = 0x10 <= src.cpuid(0x0, 0x0).eax && ((src.cpuid(0x7, 0x0).ebx >> 15) & 0b1) != 0;
+        let res_bitmap = if pqe { src.cpuid(0x10, 0x0).ebx } else { 0 };
+        let amd_bitmap = AmdPqe::ebx_bitmap(src).unwrap_or(0);
+
+        if !pqe && amd_bitmap & 0b110 == 0 {
+            return None;
+        }
+
+        let has = |bitmap: u32, bit: u32| ((bitmap >> bit) & 0b1) != 0;
+
+        Some(Self {
+            l3_cat: has(res_bitmap, 1).then(|| RdtCat::from(&src.cpuid(0x10, 0x1))),
+            l2_cat: has(res_bitmap, 2).then(|| RdtCat::from(&src.cpuid(0x10, 0x2))),
+            mba: has(res_bitmap, 3).then(|| RdtMba::from(&src.cpuid(0x10, 0x3))),
+            amd_l3_mbe: has(amd_bitmap, 1).then(|| AmdBwEnforce::from(&src.cpuid(0x8000_0020, 0x1))),
+            amd_l3_smbe: has(amd_bitmap, 2).then(|| AmdBwEnforce::from(&src.cpuid(0x8000_0020, 0x2))),
+        })
+    }
+}
+
+#[test]
+fn test_rdt() {
+    use crate::CpuidTable;
+
+    /* L3 CAT with CDP, MBA and L3 monitoring (Ice Lake-SP) */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x1B, 0x756E6547, 0x6C65746E, 0x49656E69]),
+        (0x7, 0x0, [0x0, 0xF3BFB7EF, 0x0, 0x0]),
+        (0xF, 0x0, [0x0, 0xFF, 0x0, 0x2]),
+        (0xF, 0x1, [0x0, 0xC000, 0xFF, 0x7]),
+        (0x10, 0x0, [0x0, 0xA, 0x0, 0x0]),
+        (0x10, 0x1, [0xB, 0xC00, 0x4, 0xF]),
+        (0x10, 0x3, [0x59, 0x0, 0x4, 0x7]),
+    ]);
+
+    let mon = RdtMonitoring::get_from_source(&src).unwrap();
+    let l3 = mon.l3.unwrap();
+
+    assert_eq!((mon.max_rmid, l3.upscaling_factor, l3.counter_width), (0xFF, 0xC000, 24));
+    assert!(l3.occupancy && l3.total_mbm && l3.local_mbm);
+
+    let alloc = RdtAllocation::get_from_source(&src).unwrap();
+    let l3_cat = alloc.l3_cat.unwrap();
+    let mba = alloc.mba.unwrap();
+
+    assert!(alloc.l2_cat.is_none());
+    assert_eq!((l3_cat.cbm_len, l3_cat.max_cos, l3_cat.cdp), (12, 15, true));
+    assert_eq!((mba.max_throttling, mba.max_cos, mba.linear), (90, 7, true));
+
+    /* CPUID Ryzen 5 5600G, L3 CAT and L3MBE */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let src = CpuidTable::from_raw_dump(dump);
+
+    assert_eq!(RdtMonitoring::get_from_source(&src).map(|mon| mon.max_rmid), Some(0xFF));
+
+    let alloc = RdtAllocation::get_from_source(&src).unwrap();
+
+    assert!(alloc.l3_cat.is_some() && alloc.mba.is_none() && alloc.amd_l3_smbe.is_none());
+    assert_eq!(alloc.amd_l3_mbe, Some(AmdBwEnforce { bw_len: 11, max_cos: 15 }));
+}
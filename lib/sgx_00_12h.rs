@@ -0,0 +1,169 @@
+use crate::CpuidResult;
+#[cfg(feature = "std")]
+use crate::{CpuidSource, LiveCpuid};
+
+/// Protection of the EPC section, `CPUID.(EAX=12h, ECX=n):ECX[3:0]` (n >= 2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SgxEpcProtection {
+    /// Confidentiality, integrity and replay protection
+    Full,
+    ConfidentialityOnly,
+    Unknown(u8),
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for SgxEpcProtection {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// EPC (Enclave Page Cache) section, `CPUID.(EAX=12h, ECX=n)` (n >= 2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SgxEpcSection {
+    /// Physical address of the base of the EPC section
+    pub base: u64,
+    pub size: u64,
+    pub protection: SgxEpcProtection,
+}
+
+impl SgxEpcSection {
+    /// Sub-leaf type (EAX[3:0]) 0 is Invalid, the sub-leaves after it are also invalid
+    pub fn is_invalid_type(cpuid: &CpuidResult) -> bool {
+        (cpuid.eax & 0xF) == 0x0
+    }
+
+    /// `None` if the sub-leaf type (EAX[3:0]) is not an EPC section (0: Invalid, 2..=15: Reserved)
+    pub fn option_from_cpuid(cpuid: &CpuidResult) -> Option<Self> {
+        if (cpuid.eax & 0xF) != 0x1 {
+            return None;
+        }
+
+        /* EAX[31:12]: bits 31:12 of the base, EBX[19:0]: bits 51:32 of the base */
+        let base = ((cpuid.ebx as u64 & 0xF_FFFF) << 32) | (cpuid.eax & !0xFFF) as u64;
+        /* ECX[31:12]: bits 31:12 of the size, EDX[19:0]: bits 51:32 of the size */
+        let size = ((cpuid.edx as u64 & 0xF_FFFF) << 32) | (cpuid.ecx & !0xFFF) as u64;
+        let protection = match (cpuid.ecx & 0xF) as u8 {
+            0x1 => SgxEpcProtection::Full,
+            0x2 => SgxEpcProtection::ConfidentialityOnly,
+            v => SgxEpcProtection::Unknown(v),
+        };
+
+        Some(Self { base, size, protection })
+    }
+}
+
+/// Intel SGX capability, `CPUID.(EAX=12h)`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SgxInfo {
+    /// Sub-leaf 0, EAX[0]
+    pub sgx1: bool,
+    /// Sub-leaf 0, EAX[1]: SGX2 leaf functions (EAUG, EACCEPT, EMODPR, ...)
+    pub sgx2: bool,
+    /// Sub-leaf 0, EAX[5]: EINCVIRTCHILD, EDECVIRTCHILD, ESETCONTEXT
+    pub enclv: bool,
+    /// Sub-leaf 0, EAX[6]: ETRACKC, ERDINFO, ELDBC, ELDUC
+    pub encls_c: bool,
+    /// Sub-leaf 0, EAX[7]
+    pub everifyreport2: bool,
+    /// Sub-leaf 0, EAX[10]
+    pub eupdatesvn: bool,
+    /// Sub-leaf 0, EAX[11]
+    pub edeccssa: bool,
+    /// Sub-leaf 0, EBX: Supported extended features for SSA.MISC
+    pub miscselect: u32,
+    /// Sub-leaf 0, EDX[7:0]: Maximum enclave size is 2^n bytes when not in 64-bit mode
+    pub max_enclave_size_not64: u8,
+    /// Sub-leaf 0, EDX[15:8]: Maximum enclave size is 2^n bytes in 64-bit mode
+    pub max_enclave_size_64: u8,
+    /// Sub-leaf 1, EBX:EAX: Allowed bits of SECS.ATTRIBUTES[63:0]
+    pub attributes: u64,
+    /// Sub-leaf 1, EDX:ECX: Allowed bits of SECS.ATTRIBUTES[127:64] (XFRM)
+    pub xfrm: u64,
+    /// Sub-leaf 2 <= : EPC sections, until the invalid type (reserved types are skipped)
+    pub epc_sections: Vec<SgxEpcSection>,
+}
+
+#[cfg(feature = "std")]
+impl SgxInfo {
+    /// Without the EPC sections
+    pub fn from_sub_leaf_0_1(sub0: &CpuidResult, sub1: &CpuidResult) -> Self {
+        let bit = |pos: u32| ((sub0.eax >> pos) & 0b1) != 0;
+
+        Self {
+            sgx1: bit(0),
+            sgx2: bit(1),
+            enclv: bit(5),
+            encls_c: bit(6),
+            everifyreport2: bit(7),
+            eupdatesvn: bit(10),
+            edeccssa: bit(11),
+            miscselect: sub0.ebx,
+            max_enclave_size_not64: (sub0.edx & 0xFF) as u8,
+            max_enclave_size_64: ((sub0.edx >> 8) & 0xFF) as u8,
+            attributes: ((sub1.ebx as u64) << 32) | sub1.eax as u64,
+            xfrm: ((sub1.edx as u64) << 32) | sub1.ecx as u64,
+            epc_sections: Vec::new(),
+        }
+    }
+
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if SGX (`CPUID.(EAX=07h, ECX=0):EBX[2]`) is not supported
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if src.cpuid(0x0, 0x0).eax < 0x12 || ((src.cpuid(0x7, 0x0).ebx >> 2) & 0b1) == 0 {
+            return None;
+        }
+
+        let mut info = Self::from_sub_leaf_0_1(&src.cpuid(0x12, 0x0), &src.cpuid(0x12, 0x1));
+
+        /* Reserved sub-leaf types are skipped, same as the leaf pool */
+        info.epc_sections = (0x2..0x40)
+            .map(|sub_leaf| src.cpuid(0x12, sub_leaf))
+            .take_while(|cpuid| !SgxEpcSection::is_invalid_type(cpuid))
+            .filter_map(|cpuid| SgxEpcSection::option_from_cpuid(&cpuid))
+            .collect();
+
+        Some(info)
+    }
+}
+
+#[test]
+fn test_sgx_info() {
+    use crate::CpuidTable;
+
+    /* SGX1, SGX2 and a 64 GiB EPC section at 0x80_0000_0000 */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x1B, 0x756E6547, 0x6C65746E, 0x49656E69]),
+        (0x7, 0x0, [0x0, 0x4, 0x0, 0x0]),
+        (0x12, 0x0, [0x3, 0x0, 0x0, 0x381F]),
+        (0x12, 0x1, [0xB6, 0x0, 0x2E7, 0x0]),
+        (0x12, 0x2, [0x1, 0x80, 0x1, 0x10]),
+        /* reserved type */
+        (0x12, 0x3, [0x2, 0x0, 0x0, 0x0]),
+        (0x12, 0x4, [0x80000001, 0x0, 0x80000001, 0x0]),
+        (0x12, 0x5, [0x0, 0x0, 0x0, 0x0]),
+        (0x12, 0x6, [0x1, 0x1, 0x1, 0x1]),
+    ]);
+
+    let sgx = SgxInfo::get_from_source(&src).unwrap();
+
+    assert!(sgx.sgx1 && sgx.sgx2 && !sgx.edeccssa);
+    assert_eq!((sgx.max_enclave_size_not64, sgx.max_enclave_size_64), (31, 56));
+    assert_eq!((sgx.attributes, sgx.xfrm), (0xB6, 0x2E7));
+    assert_eq!(sgx.epc_sections, [SgxEpcSection {
+        base: 0x80_0000_0000,
+        size: 64 << 30,
+        protection: SgxEpcProtection::Full,
+    }, SgxEpcSection {
+        base: 0x8000_0000,
+        size: 0x8000_0000,
+        protection: SgxEpcProtection::Full,
+    }]);
+
+    let cpuid = CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 };
+    assert!(SgxEpcSection::option_from_cpuid(&cpuid).is_none());
+}
@@ -0,0 +1,419 @@
+use crate::{CacheProp, CpuidResult, CpuidSource, CpuVendor, HypervisorInfo, LiveCpuid, RawCpuid, SgxEpcSection, TopoId};
+use crate::util::{cpu_set_list, pin_thread};
+
+use std::thread;
+#[cfg(target_os = "linux")]
+use std::io;
+#[cfg(target_os = "linux")]
+use crate::CpuidDev;
+
+/// List of Leaf/Sub-leaf available on the CPU
+pub fn leaf_pool() -> Vec<(u32, u32)> {
+    leaf_pool_from_source(&LiveCpuid)
+}
+
+pub fn leaf_pool_from_source<S: CpuidSource>(src: &S) -> Vec<(u32, u32)> {
+    let mut leaf_pool: Vec<(u32, u32)> = Vec::with_capacity(64);
+
+    /* LFuncStd: largest standard function */
+    let max_std_leaf = src.cpuid(0x0, 0x0).eax;
+    /* LFuncExt: largest extended function */
+    let max_ext_leaf = src.cpuid(0x8000_0000, 0x0).eax;
+
+    /* Base */
+    for leaf in 0x0..=max_std_leaf {
+        match leaf {
+            /* Cache Properties, Intel */
+            0x4 => push_sub_leaf_until(src, &mut leaf_pool, leaf, is_null_cache_type),
+            /*  0x7: Structured Extended Feature Flags, EAX: StructExtFeatIdMax
+                0x14: Intel Processor Trace
+                0x17: SoC Vendor Attribute
+                0x18: Deterministic Address Translation Parameters
+                0x1D: Tile Information (AMX), EAX: max_palette
+                0x20: Processor History Reset
+                0x24: AVX10 Converged Vector ISA
+            */
+            0x7 | 0x14 | 0x17 | 0x18 | 0x1D | 0x20 | 0x24 => {
+                let max_sub_leaf = src.cpuid(leaf, 0x0).eax.min(SUB_LEAF_LIMIT);
+
+                for sub_leaf in 0x0..=max_sub_leaf {
+                    leaf_pool.push((leaf, sub_leaf))
+                }
+            },
+            /*  Extended Topology Enumeration, Intel, AMD Zen 2 <=
+                0x1F: V2 Extended Topology Enumeration, Intel
+            */
+            0xB | 0x1F => push_sub_leaf_until(src, &mut leaf_pool, leaf, is_invalid_level_type),
+            /* 0xD: Processor Extended State Enumeration */
+            0xD => {
+                let [sub_00h, sub_01h] = [0x0, 0x1].map(|sub_leaf| src.cpuid(leaf, sub_leaf));
+                /* XCR0: EDX:EAX of Sub-leaf 0, IA32_XSS: EDX:ECX of Sub-leaf 1 */
+                let xcr0 = ((sub_00h.edx as u64) << 32) | sub_00h.eax as u64;
+                let xss = ((sub_01h.edx as u64) << 32) | sub_01h.ecx as u64;
+
+                leaf_pool.push((leaf, 0x0));
+                leaf_pool.push((leaf, 0x1));
+                push_sub_leaf_bitmap(&mut leaf_pool, leaf, xcr0 | xss, 2);
+            },
+            /* 0xF: Resource Director Technology Monitoring, EDX: Resource type bitmap */
+            0xF => {
+                let bitmap = src.cpuid(leaf, 0x0).edx as u64;
+
+                leaf_pool.push((leaf, 0x0));
+                push_sub_leaf_bitmap(&mut leaf_pool, leaf, bitmap, 1);
+            },
+            /* 0x10: Resource Director Technology Allocation, EBX: Resource ID bitmap */
+            0x10 => {
+                let bitmap = src.cpuid(leaf, 0x0).ebx as u64;
+
+                leaf_pool.push((leaf, 0x0));
+                push_sub_leaf_bitmap(&mut leaf_pool, leaf, bitmap, 1);
+            },
+            /* 0x12: Intel SGX, Sub-leaf 2 <= : EPC sections, until the invalid type (EAX[3:0] == 0) */
+            0x12 if ((src.cpuid(0x7, 0x0).ebx >> 2) & 0b1) == 0 => leaf_pool.push((leaf, 0x0)),
+            0x12 => {
+                leaf_pool.push((leaf, 0x0));
+                leaf_pool.push((leaf, 0x1));
+
+                for sub_leaf in 0x2..SUB_LEAF_LIMIT {
+                    leaf_pool.push((leaf, sub_leaf));
+
+                    if SgxEpcSection::is_invalid_type(&src.cpuid(leaf, sub_leaf)) { break }
+                }
+            },
+            /* 0x1B: PCONFIG Information, until the invalid sub-leaf type */
+            0x1B => push_sub_leaf_until(src, &mut leaf_pool, leaf, is_invalid_pconfig_type),
+            /* 0x23: Architectural Performance Monitoring Extended, EAX: Valid sub-leaves bitmap */
+            0x23 => {
+                let bitmap = src.cpuid(leaf, 0x0).eax as u64;
+
+                leaf_pool.push((leaf, 0x0));
+                push_sub_leaf_bitmap(&mut leaf_pool, leaf, bitmap, 1);
+            },
+            _ => leaf_pool.push((leaf, 0x0)),
+        }
+    }
+
+    /* Hypervisor, 0x4000_0000 and the ranges in the stride of 0x100 (e.g. KVM with Hyper-V enlightenments) */
+    for hv in HypervisorInfo::list_from_source(src) {
+        for leaf in hv.base..=hv.max_leaf {
+            leaf_pool.push((leaf, 0x0))
+        }
+    }
+
+    /* Ext */
+    for leaf in 0x8000_0000..=max_ext_leaf {
+        match leaf {
+            /* Cache Properties, AMD, same format as Intel Leaf 0x4 */
+            0x8000_001D => push_sub_leaf_until(src, &mut leaf_pool, leaf, is_null_cache_type),
+            /* AMD Platform QoS Enforcement for Memory Bandwidth, EBX: Sub-leaf bitmap */
+            0x8000_0020 => {
+                let bitmap = src.cpuid(leaf, 0x0).ebx as u64;
+
+                leaf_pool.push((leaf, 0x0));
+                push_sub_leaf_bitmap(&mut leaf_pool, leaf, bitmap, 1);
+            },
+            /* AMD Extended CPU Topology */
+            0x8000_0026 => push_sub_leaf_until(src, &mut leaf_pool, leaf, is_invalid_level_type),
+            _ => leaf_pool.push((leaf, 0x0)),
+        }
+    }
+
+    /* Centaur/Zhaoxin extended range, other vendors return the result of the highest basic leaf */
+    if matches!(CpuVendor::get_from_source(src), CpuVendor::CentaurHauls | CpuVendor::Shanghai) {
+        let max_centaur_leaf = src.cpuid(0xC000_0000, 0x0).eax;
+
+        if (0xC000_0000..=0xC000_00FF).contains(&max_centaur_leaf) {
+            for leaf in 0xC000_0000..=max_centaur_leaf {
+                leaf_pool.push((leaf, 0x0))
+            }
+        }
+    }
+
+    leaf_pool
+}
+
+/* Upper limit of Sub-leaf, in case the terminator is not returned (e.g. broken hypervisor) */
+const SUB_LEAF_LIMIT: u32 = 0x40;
+
+/* Cache Type: EAX[4:0], 0 is Null (no more caches) */
+fn is_null_cache_type(cpuid: &CpuidResult) -> bool {
+    (cpuid.eax & 0x1F) == 0x0
+}
+
+/* Level Type: ECX[15:8], 0 is Invalid */
+fn is_invalid_level_type(cpuid: &CpuidResult) -> bool {
+    ((cpuid.ecx >> 8) & 0xFF) == 0x0
+}
+
+/* Sub-leaf Type: EAX[11:0], 0 is Invalid */
+fn is_invalid_pconfig_type(cpuid: &CpuidResult) -> bool {
+    (cpuid.eax & 0xFFF) == 0x0
+}
+
+/* Sub-leaves from 0 to the terminator (inclusive) */
+fn push_sub_leaf_until<S: CpuidSource>(
+    src: &S,
+    leaf_pool: &mut Vec<(u32, u32)>,
+    leaf: u32,
+    is_end: fn(&CpuidResult) -> bool,
+) {
+    for sub_leaf in 0x0..SUB_LEAF_LIMIT {
+        leaf_pool.push((leaf, sub_leaf));
+
+        if is_end(&src.cpuid(leaf, sub_leaf)) { break }
+    }
+}
+
+/* Sub-leaves whose bit is set in the bitmap */
+fn push_sub_leaf_bitmap(leaf_pool: &mut Vec<(u32, u32)>, leaf: u32, bitmap: u64, start: u32) {
+    for sub_leaf in start..64 {
+        if ((bitmap >> sub_leaf) & 0b1) == 0b1 {
+            leaf_pool.push((leaf, sub_leaf))
+        }
+    }
+}
+
+/// CPUID results of a thread, with the CPU vendor and topology ID
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub cpu_vendor: CpuVendor,
+    pub rawcpuid_pool: Vec<RawCpuid>,
+    pub topo_id: Option<TopoId>,
+    pub thread_id: Option<usize>,
+}
+
+impl Snapshot {
+    /// Snapshot of the current thread
+    pub fn new(leaf_pool: &[(u32, u32)], skip_zero: bool) -> Self {
+        Self::from_source(&LiveCpuid, leaf_pool, skip_zero)
+    }
+
+    pub fn from_source<S: CpuidSource>(src: &S, leaf_pool: &[(u32, u32)], skip_zero: bool) -> Self {
+        let cpu_vendor = CpuVendor::get_from_source(src);
+
+        let rawcpuid_pool = leaf_pool.iter().filter_map(|(leaf, sub_leaf)| {
+            let rawcpuid = RawCpuid::exe_from_source(src, *leaf, *sub_leaf);
+
+            if skip_zero && rawcpuid.check_result_zero() {
+                None
+            } else {
+                Some(rawcpuid)
+            }
+        }).collect();
+        let topo_id = TopoId::get_topo_info_from_source(src);
+
+        Self {
+            cpu_vendor,
+            rawcpuid_pool,
+            topo_id,
+            thread_id: None,
+        }
+    }
+
+    /// Snapshot from recorded CPUID results (e.g. dump files),
+    /// the CPU vendor and topology ID are decoded from them.
+    pub fn from_rawcpuid_pool(rawcpuid_pool: Vec<RawCpuid>, thread_id: Option<usize>) -> Self {
+        let cpu_vendor = CpuVendor::get_from_source(&rawcpuid_pool.as_slice());
+        let topo_id = TopoId::get_topo_info_from_source(&rawcpuid_pool.as_slice());
+
+        Self {
+            cpu_vendor,
+            rawcpuid_pool,
+            topo_id,
+            thread_id,
+        }
+    }
+
+    pub fn new_with_thread_id(leaf_pool: &[(u32, u32)], skip_zero: bool, thread_id: usize) -> Self {
+        let mut tmp = Self::new(leaf_pool, skip_zero);
+        tmp.thread_id = Some(thread_id);
+
+        tmp
+    }
+
+    /// Snapshots of the specified CPUs, the thread is pinned to each CPU
+    pub fn from_cpu_list(
+        cpu_list: &[usize],
+        leaf_pool: &[(u32, u32)],
+        skip_zero: bool,
+    ) -> Result<Vec<Self>, i32> {
+        /* To confine the effects of pin_thread */
+        thread::scope(|s| {
+            let handles: Vec<thread::ScopedJoinHandle<_>> = cpu_list.iter().map(|cpu| {
+                let cpu = *cpu;
+
+                s.spawn(move || -> Result<Self, i32> {
+                    pin_thread(cpu)?;
+
+                    Ok(Self::new_with_thread_id(leaf_pool, skip_zero, cpu))
+                })
+            }).collect();
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        })
+    }
+
+    /// Snapshots of all available CPUs
+    pub fn all_threads(leaf_pool: &[(u32, u32)], skip_zero: bool) -> Result<Vec<Self>, i32> {
+        let cpu_list = cpu_set_list()?;
+
+        Self::from_cpu_list(&cpu_list, leaf_pool, skip_zero)
+    }
+
+    /// Snapshots of the specified CPUs, read from the Linux cpuid device (`/dev/cpu/N/cpuid`)
+    /// instead of pinning the thread
+    #[cfg(target_os = "linux")]
+    pub fn from_cpuid_dev(
+        cpu_list: &[usize],
+        leaf_pool: &[(u32, u32)],
+        skip_zero: bool,
+    ) -> io::Result<Vec<Self>> {
+        cpu_list.iter().map(|cpu| {
+            let dev = CpuidDev::open(*cpu)?;
+
+            Self::try_from_cpuid_dev(&dev, leaf_pool, skip_zero)
+        }).collect()
+    }
+
+    /// Snapshot of the CPU of the cpuid device, fails if reading any Leaf/Sub-leaf fails
+    #[cfg(target_os = "linux")]
+    pub fn try_from_cpuid_dev(dev: &CpuidDev, leaf_pool: &[(u32, u32)], skip_zero: bool) -> io::Result<Self> {
+        let mut rawcpuid_pool = Vec::with_capacity(leaf_pool.len());
+
+        for (leaf, sub_leaf) in leaf_pool {
+            let rawcpuid = RawCpuid {
+                leaf: *leaf,
+                sub_leaf: *sub_leaf,
+                result: dev.read(*leaf, *sub_leaf)?,
+            };
+
+            if skip_zero && rawcpuid.check_result_zero() {
+                continue;
+            }
+
+            rawcpuid_pool.push(rawcpuid);
+        }
+
+        Ok(Self::from_rawcpuid_pool(rawcpuid_pool, Some(dev.cpu)))
+    }
+
+    /// Snapshots of all CPUs with the cpuid device
+    #[cfg(target_os = "linux")]
+    pub fn all_threads_from_cpuid_dev(leaf_pool: &[(u32, u32)], skip_zero: bool) -> io::Result<Vec<Self>> {
+        let cpu_list = CpuidDev::cpu_list()?;
+
+        Self::from_cpuid_dev(&cpu_list, leaf_pool, skip_zero)
+    }
+
+    /// Cache properties from the sub-leaves of the leaf 0x4 (Intel) or 0x8000_001D (AMD) in the snapshot
+    pub fn cache_props(&self) -> impl Iterator<Item = CacheProp> + '_ {
+        let cache_leaf = CacheProp::get_cache_prop_leaf_from_source(self);
+
+        self.rawcpuid_pool
+            .iter()
+            .filter(move |rawcpuid| Some(rawcpuid.leaf) == cache_leaf)
+            .filter_map(|rawcpuid| CacheProp::option_from_cpuid(&rawcpuid.result))
+    }
+
+    /// Remove the results that are the same as `base` (e.g. the first thread)
+    pub fn retain_diff(&mut self, base: &Self) {
+        let mut base_rawcpuid_pool = base.rawcpuid_pool.iter();
+
+        self.rawcpuid_pool.retain(|sub| {
+            let Some(base) = base_rawcpuid_pool.next() else { return false };
+            base != sub
+        });
+    }
+
+    /// Restore the results removed by `retain_diff` from `base`
+    pub fn restore_diff(&mut self, base: &Self) {
+        let mut rawcpuid_pool: Vec<RawCpuid> = base.rawcpuid_pool.iter().map(|base| {
+            *self.rawcpuid_pool
+                .iter()
+                .find(|sub| sub.leaf == base.leaf && sub.sub_leaf == base.sub_leaf)
+                .unwrap_or(base)
+        }).collect();
+
+        for sub in &self.rawcpuid_pool {
+            if !rawcpuid_pool.iter().any(|r| r.leaf == sub.leaf && r.sub_leaf == sub.sub_leaf) {
+                rawcpuid_pool.push(*sub);
+            }
+        }
+
+        self.rawcpuid_pool = rawcpuid_pool;
+    }
+}
+
+/// Replay the recorded CPUID results,
+/// Leaf/Sub-leaf not in the results return all zero.
+impl CpuidSource for [RawCpuid] {
+    fn cpuid(&self, leaf: u32, sub_leaf: u32) -> CpuidResult {
+        self.iter()
+            .find(|rawcpuid| rawcpuid.leaf == leaf && rawcpuid.sub_leaf == sub_leaf)
+            .map(|rawcpuid| rawcpuid.result)
+            .unwrap_or(CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 })
+    }
+}
+
+impl CpuidSource for Snapshot {
+    fn cpuid(&self, leaf: u32, sub_leaf: u32) -> CpuidResult {
+        self.rawcpuid_pool.as_slice().cpuid(leaf, sub_leaf)
+    }
+}
+
+#[test]
+fn test_leaf_pool_sub_leaf() {
+    use crate::CpuidTable;
+
+    /* CPUID Ryzen 5 5600G */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let src = CpuidTable::from_raw_dump(dump);
+    let leaf_pool = leaf_pool_from_source(&src);
+    let sub_leaves = |leaf: u32| -> Vec<u32> {
+        leaf_pool.iter().filter(|(l, _)| *l == leaf).map(|(_, sub_leaf)| *sub_leaf).collect()
+    };
+
+    /* until the invalid level type (inclusive) */
+    assert_eq!(sub_leaves(0xB), [0, 1, 2]);
+    /* XCR0: 0x207, IA32_XSS: 0x1800 */
+    assert_eq!(sub_leaves(0xD), [0, 1, 2, 9, 11, 12]);
+    /* until the null cache type (inclusive) */
+    assert_eq!(sub_leaves(0x8000_001D), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn test_snapshot_cache_props() {
+    use crate::CpuidTable;
+
+    /* CPUID Ryzen 5 5600G: L1d, L1i, L2, L3 */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let src = CpuidTable::from_raw_dump(dump);
+    let snapshot = Snapshot::from_source(&src, &leaf_pool_from_source(&src), true);
+    let levels: Vec<u32> = snapshot.cache_props().map(|cache| cache.level).collect();
+
+    assert_eq!(levels, [1, 1, 2, 3]);
+}
+
+#[test]
+fn test_leaf_pool_centaur() {
+    use crate::CpuidTable;
+
+    let centaur_leaves = [
+        (0xC000_0000, 0x0, [0xC000_0001, 0x0, 0x0, 0x0]),
+        (0xC000_0001, 0x0, [0x0, 0x0, 0x0, 0x3FCC]),
+    ];
+    /* "  Shanghai  " */
+    let mut regs = vec![(0x0, 0x0, [0x1, 0x68532020, 0x20206961, 0x68676E61])];
+    regs.extend(centaur_leaves);
+    let leaf_pool = leaf_pool_from_source(&CpuidTable::from_regs(&regs));
+
+    assert!(leaf_pool.contains(&(0xC000_0000, 0x0)) && leaf_pool.contains(&(0xC000_0001, 0x0)));
+
+    /* not enumerated for other vendors */
+    let mut regs = vec![(0x0, 0x0, [0x1, 0x756E6547, 0x6C65746E, 0x49656E69])];
+    regs.extend(centaur_leaves);
+    let leaf_pool = leaf_pool_from_source(&CpuidTable::from_regs(&regs));
+
+    assert!(!leaf_pool.iter().any(|(leaf, _)| *leaf >= 0xC000_0000));
+}
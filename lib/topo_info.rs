@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult, TopoLevelType};
+use crate::{CpuidResult, CpuidSource, LiveCpuid, TopoLevelType};
 
 /// Topology ID (SMT, Core, Pkg, X2APIC)
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -10,18 +10,18 @@ pub struct TopoId {
 }
 
 impl TopoId {
-    fn check_topology_leaf(leaf: u32) -> bool {
+    fn check_topology_leaf<S: CpuidSource>(src: &S, leaf: u32) -> bool {
         const SUB_LEAF: u32 = 0x1;
-        let cpuid = cpuid!(leaf, SUB_LEAF);
+        let cpuid = src.cpuid(leaf, SUB_LEAF);
 
         /* ECX[07-00]: Level number. Same value in ECX input (Sub_Leaf) */
         (cpuid.ecx & 0xFF) == SUB_LEAF
     }
 
-    pub(crate) fn get_topology_leaf() -> Option<u32> {
-        let topo_leaf = if Self::check_topology_leaf(0x1F) {
+    pub(crate) fn get_topology_leaf<S: CpuidSource>(src: &S) -> Option<u32> {
+        let topo_leaf = if Self::check_topology_leaf(src, 0x1F) {
             0x1F
-        } else if Self::check_topology_leaf(0xB) {
+        } else if Self::check_topology_leaf(src, 0xB) {
             0xB
         } else {
             return None;
@@ -30,12 +30,13 @@ impl TopoId {
         Some(topo_leaf)
     }
 
-    pub(crate) fn get_cpuid_by_level_type(
+    pub(crate) fn get_cpuid_by_level_type<S: CpuidSource>(
+        src: &S,
         topo_leaf: u32,
         target_level_type: TopoLevelType
     ) -> Option<CpuidResult> {
         for sub_leaf in 0..(TopoLevelType::Die as u32) {
-            let cpuid = cpuid!(topo_leaf, sub_leaf);
+            let cpuid = src.cpuid(topo_leaf, sub_leaf);
             let level_type = TopoLevelType::from(&cpuid);
             
             if level_type == target_level_type {
@@ -77,10 +78,14 @@ impl TopoId {
         https://www.intel.com/content/dam/develop/external/us/en/documents/kuo-cputopology-rc1-rh1-final-256920.pdf
     */
     pub fn get_topo_info() -> Option<Self> {
-        let topo_leaf = Self::get_topology_leaf()?;
+        Self::get_topo_info_from_source(&LiveCpuid)
+    }
+
+    pub fn get_topo_info_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        let topo_leaf = Self::get_topology_leaf(src)?;
 
-        let smt_cpuid = Self::get_cpuid_by_level_type(topo_leaf, TopoLevelType::SMT)?;
-        let core_cpuid = Self::get_cpuid_by_level_type(topo_leaf, TopoLevelType::Core)?;
+        let smt_cpuid = Self::get_cpuid_by_level_type(src, topo_leaf, TopoLevelType::SMT)?;
+        let core_cpuid = Self::get_cpuid_by_level_type(src, topo_leaf, TopoLevelType::Core)?;
 
         Some(Self::get_topo_info_with_smt_core_cpuid(&smt_cpuid, &core_cpuid))
     }
@@ -0,0 +1,147 @@
+use crate::{CpuCodename, CpuidResult, CpuidSource, CpuVendor, FamModStep, IntelCodename, LiveCpuid, ProcInfo};
+
+/// Where the core crystal clock frequency of [TscFreq] comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrystalSource {
+    /// `CPUID.(EAX=15h):ECX`
+    Leaf15h,
+    /// Known nominal frequency of the model (Intel SDM Vol.3B, "Determining the Processor Base Frequency")
+    KnownModel,
+    /// Calculated from the processor base frequency of `CPUID.(EAX=16h):EAX` and the TSC/crystal ratio
+    Leaf16h,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for CrystalSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Nominal TSC frequency, `CPUID.(EAX=15h)`.
+/// AMD CPUs report neither the TSC/crystal ratio nor the crystal frequency (TSC runs at the P0 frequency),
+/// so only `invariant` is available there unless the leaf 0x15 is reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TscFreq {
+    /// EAX: Denominator of the TSC/"core crystal clock" ratio
+    pub denominator: u32,
+    /// EBX: Numerator of the TSC/"core crystal clock" ratio
+    pub numerator: u32,
+    pub crystal_hz: u64,
+    pub crystal_source: CrystalSource,
+    pub tsc_hz: u64,
+    /// Invariant TSC: `CPUID.(EAX=8000_0007h):EDX[8]`
+    pub invariant: bool,
+}
+
+impl TscFreq {
+    /// Nominal core crystal clock frequency of the models that report 0 in `CPUID.(EAX=15h):ECX`
+    pub fn known_crystal_hz(codename: &CpuCodename) -> Option<u64> {
+        let CpuCodename::Intel(codename) = codename else { return None };
+
+        let hz = match codename {
+            IntelCodename::SkyLake_L |
+            IntelCodename::SkyLake_S |
+            IntelCodename::KabyLake_L |
+            IntelCodename::KabyLake_S |
+            IntelCodename::AmberLake_L |
+            IntelCodename::CoffeeLake_L |
+            IntelCodename::WhiskeyLake_L |
+            IntelCodename::CoffeeLake_S => 24_000_000,
+            IntelCodename::SkyLake_X |
+            IntelCodename::CascadeLake_X |
+            IntelCodename::CooperLake_X |
+            IntelCodename::Denverton => 25_000_000,
+            IntelCodename::ApolloLake |
+            IntelCodename::GeminiLake => 19_200_000,
+            _ => return None,
+        };
+
+        Some(hz)
+    }
+
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    /// `None` if the TSC/crystal ratio is not enumerated, or the crystal clock frequency is unknown
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        let max_leaf = src.cpuid(0x0, 0x0).eax;
+
+        if max_leaf < 0x15 {
+            return None;
+        }
+
+        let CpuidResult { eax: denominator, ebx: numerator, ecx: crystal, .. } = src.cpuid(0x15, 0x0);
+
+        if denominator == 0 || numerator == 0 {
+            return None;
+        }
+
+        let (crystal_hz, crystal_source) = if crystal != 0 {
+            (crystal as u64, CrystalSource::Leaf15h)
+        } else {
+            let vendor = CpuVendor::get_from_source(src);
+            let codename = ProcInfo::from_fms(&FamModStep::get_from_source(src), &vendor).codename;
+            let base_mhz = if 0x16 <= max_leaf { src.cpuid(0x16, 0x0).eax & 0xFFFF } else { 0 };
+
+            if let Some(hz) = Self::known_crystal_hz(&codename) {
+                (hz, CrystalSource::KnownModel)
+            } else if base_mhz != 0 {
+                (
+                    base_mhz as u64 * 1_000_000 * denominator as u64 / numerator as u64,
+                    CrystalSource::Leaf16h,
+                )
+            } else {
+                return None;
+            }
+        };
+
+        let tsc_hz = crystal_hz * numerator as u64 / denominator as u64;
+        let invariant = {
+            let max_ext_leaf = src.cpuid(0x8000_0000, 0x0).eax;
+
+            0x8000_0007 <= max_ext_leaf && ((src.cpuid(0x8000_0007, 0x0).edx >> 8) & 0b1) != 0
+        };
+
+        Some(Self {
+            denominator,
+            numerator,
+            crystal_hz,
+            crystal_source,
+            tsc_hz,
+            invariant,
+        })
+    }
+}
+
+#[test]
+fn test_tsc_freq() {
+    use crate::CpuidTable;
+
+    let vendor = [0x16, 0x756E6547, 0x6C65746E, 0x49656E69];
+
+    /* Core i5-12400: 38.4 MHz crystal */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, vendor),
+        (0x15, 0x0, [0x2, 0x82, 0x249F000, 0x0]),
+        (0x8000_0000, 0x0, [0x8000_0008, 0x0, 0x0, 0x0]),
+        (0x8000_0007, 0x0, [0x0, 0x0, 0x0, 0x100]),
+    ]);
+    let tsc = TscFreq::get_from_source(&src).unwrap();
+
+    assert_eq!((tsc.crystal_hz, tsc.crystal_source), (38_400_000, CrystalSource::Leaf15h));
+    assert_eq!(tsc.tsc_hz, 2_496_000_000);
+    assert!(tsc.invariant);
+
+    /* SkyLake_S (06_5EH) reports ECX = 0, 24 MHz crystal */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, vendor),
+        (0x1, 0x0, [0x506E3, 0x0, 0x0, 0x0]),
+        (0x15, 0x0, [0x2, 0x11A, 0x0, 0x0]),
+    ]);
+    let tsc = TscFreq::get_from_source(&src).unwrap();
+
+    assert_eq!((tsc.crystal_hz, tsc.crystal_source), (24_000_000, CrystalSource::KnownModel));
+    assert_eq!(tsc.tsc_hz, 3_384_000_000);
+}
@@ -1,4 +1,4 @@
-use crate::{cpuid, CacheProp, TopoId, TopoLevelType};
+use crate::{CacheProp, CpuidSource, LiveCpuid, TopoId, TopoLevelType};
 
 /// Pin thread to CPU
 pub fn pin_thread(cpu: usize) -> Result<(), i32> {
@@ -82,25 +82,39 @@ pub fn cpu_set_list() -> Result<Vec<usize>, i32> {
 }
 
 pub fn get_total_logical_processor() -> Option<u32> {
-    if let Some(topo_leaf) = TopoId::get_topology_leaf() {
-        let thread_count = (cpuid!(topo_leaf, 0x1).ebx >> 16) & 0xFF;
+    get_total_logical_processor_from_source(&LiveCpuid)
+}
+
+pub fn get_total_logical_processor_from_source<S: CpuidSource>(src: &S) -> Option<u32> {
+    if let Some(topo_leaf) = TopoId::get_topology_leaf(src) {
+        /* EBX[15:0]: Number of logical processors at this level type */
+        let thread_count = src.cpuid(topo_leaf, 0x1).ebx & 0xFFFF;
 
-        return Some(thread_count);
+        Some(thread_count)
     } else {
-        let leaf_01h = cpuid!(0x1, 0x0);
-        let proc_count = ((leaf_01h.ebx >> 16) & 0xFF) + 1;
+        let leaf_01h = src.cpuid(0x1, 0x0);
+
+        /* EBX[23:16] is valid if HTT (EDX[28]) is set */
+        if ((leaf_01h.edx >> 28) & 0b1) == 0 { return Some(1); }
+
+        /* EBX[23:16]: Maximum number of addressable IDs for logical processors in the package */
+        let proc_count = (leaf_01h.ebx >> 16) & 0xFF;
 
         if proc_count == 0 { return None; }
 
-        return Some(proc_count);
+        Some(proc_count)
     }
 }
 
 pub fn get_threads_per_core() -> Option<u32> {
+    get_threads_per_core_from_source(&LiveCpuid)
+}
+
+pub fn get_threads_per_core_from_source<S: CpuidSource>(src: &S) -> Option<u32> {
     /* Extended Topology Enumeration */
-    if let Some(topo_leaf) = TopoId::get_topology_leaf() {
+    if let Some(topo_leaf) = TopoId::get_topology_leaf(src) {
         /* SMT Level */
-        let cpuid = cpuid!(topo_leaf, 0x0);
+        let cpuid = src.cpuid(topo_leaf, 0x0);
         let level = (cpuid.ecx >> 8) & 0xFF;
 
         if level == (TopoLevelType::SMT as u32) {
@@ -111,18 +125,19 @@ pub fn get_threads_per_core() -> Option<u32> {
     /*
         AMD TopologyExtensions flag: CPUID[Leaf=0x8000_0001, SubLeaf=0x0].ECX[22]
     */
-    let check_topoext = ((cpuid!(0x8000_0001, 0x0).ecx >> 22) & 0b1) != 0;
+    let check_topoext = ((src.cpuid(0x8000_0001, 0x0).ecx >> 22) & 0b1) != 0;
     if check_topoext {
-        let cpuid = cpuid!(0x8000_001E, 0x0).ebx;
-        let per_core = (cpuid >> 8) & 0xFF;
+        let cpuid = src.cpuid(0x8000_001E, 0x0).ebx;
+        /* EBX[15:8]: ThreadsPerComputeUnit, the number of threads per core is the value + 1 */
+        let per_core = ((cpuid >> 8) & 0xFF) + 1;
 
         return Some(per_core);
     }
 
     /* Cache Parameters/Properties */
-    if let Some(cache_leaf) = CacheProp::get_cache_prop_leaf() {
+    if let Some(cache_leaf) = CacheProp::get_cache_prop_leaf_from_source(src) {
         /* L1 Data Cache or L1 Instruction Cache */
-        let cpuid = cpuid!(cache_leaf, 0x0);
+        let cpuid = src.cpuid(cache_leaf, 0x0);
         let cache_prop = CacheProp::from(&cpuid);
 
         if cache_prop.level != 1 {
@@ -155,3 +170,33 @@ macro_rules! max_apic_id {
         ($ebx >> 16) & 0xFF
     };
 }
+
+#[test]
+fn test_total_logical_processor() {
+    use crate::CpuidTable;
+
+    /* CPUID Ryzen 5 5600G, 0xB_x1: EBX = 0x0000000C */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let src = CpuidTable::from_raw_dump(dump);
+
+    assert_eq!(get_total_logical_processor_from_source(&src), Some(12));
+
+    /* CPUID Ryzen 5 2600, without the leaf 0xB */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x0000000D, 0x68747541, 0x444D4163, 0x69746E65]),
+        (0x1, 0x0, [0x00800F82, 0x000C0800, 0x7ED8320B, 0x178BFBFF]),
+        (0x8000_0001, 0x0, [0x00800F82, 0x20000000, 0x35C233FF, 0x2FD3FBFF]),
+        (0x8000_001E, 0x0, [0x00000000, 0x00000100, 0x00000000, 0x00000000]),
+    ]);
+
+    assert_eq!(get_total_logical_processor_from_source(&src), Some(12));
+    assert_eq!(get_threads_per_core_from_source(&src), Some(2));
+
+    /* single thread, HTT (EDX[28]) is clear */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x1, 0x756E6547, 0x6C65746E, 0x49656E69]),
+        (0x1, 0x0, [0x0, 0x00010800, 0x0, 0x0]),
+    ]);
+
+    assert_eq!(get_total_logical_processor_from_source(&src), Some(1));
+}
@@ -1,4 +1,4 @@
-use crate::{cpuid, CpuidResult};
+use crate::{CpuidResult, CpuidSource, LiveCpuid};
 
 /* ref: https://github.com/llvm/llvm-project/blob/main/clang/lib/Headers/cpuid.h */
 /* ref: https://github.com/gcc-mirror/gcc/blob/master/gcc/config/i386/cpuid.h */
@@ -49,7 +49,11 @@ impl Vendor {
     };
 
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x0, 0x0))
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x0, 0x0))
     }
 }
 
@@ -100,7 +104,7 @@ impl From<&Vendor> for CpuVendor {
             Vendor::INTEL_EBX => Self::GenuineIntel,
             Vendor::CENTAUR_EBX => Self::CentaurHauls,
             Vendor::SHANGHAI_EBX => Self::Shanghai,
-            _ => Self::Unknown(vendor.clone()),
+            _ => Self::Unknown(*vendor),
         }
     }
 }
@@ -113,7 +117,11 @@ impl From<&CpuidResult> for CpuVendor {
 
 impl CpuVendor {
     pub fn get() -> Self {
-        Self::from(&cpuid!(0x0, 0x0))
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Self {
+        Self::from(&src.cpuid(0x0, 0x0))
     }
 }
 
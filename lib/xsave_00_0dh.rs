@@ -0,0 +1,218 @@
+use crate::CpuidResult;
+#[cfg(feature = "std")]
+use crate::{CpuidSource, LiveCpuid};
+
+/// Size of the legacy region (x87, SSE) and the XSAVE header
+pub const XSAVE_LEGACY_HEADER_SIZE: u32 = 512 + 64;
+
+/// State components saved by XSAVE/XSAVES
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XStateKind {
+    /// Managed by XCR0
+    User,
+    /// Managed by IA32_XSS
+    Supervisor,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for XStateKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// State component `i` of `CPUID.(EAX=0Dh, ECX=i)` (i >= 2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XStateComponent {
+    pub index: u8,
+    /// EAX: Size in bytes
+    pub size: u32,
+    /// EBX: Offset in the standard format, 0 for the supervisor state
+    pub offset: u32,
+    /// ECX[0]
+    pub kind: XStateKind,
+    /// ECX[1]: Aligned to the 64-byte boundary in the compacted format
+    pub align_64: bool,
+    /// ECX[2]: Extended Feature Disable (XFD) supported
+    pub xfd: bool,
+}
+
+impl XStateComponent {
+    /* Ref: https://github.com/torvalds/linux/blob/master/arch/x86/kernel/fpu/xstate.c */
+    pub fn name(index: u8) -> &'static str {
+        match index {
+            0 => "x87",
+            1 => "SSE",
+            2 => "AVX",
+            3 => "MPX BNDREGS",
+            4 => "MPX BNDCSR",
+            5 => "AVX-512 opmask",
+            6 => "AVX-512 ZMM_Hi256",
+            7 => "AVX-512 Hi16_ZMM",
+            8 => "PT",
+            9 => "PKRU",
+            10 => "PASID",
+            11 => "CET User",
+            12 => "CET Supervisor",
+            13 => "HDC",
+            14 => "UINTR",
+            15 => "LBR",
+            16 => "HWP",
+            17 => "AMX TILECFG",
+            18 => "AMX TILEDATA",
+            19 => "APX",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn from_cpuid(index: u8, cpuid: &CpuidResult) -> Self {
+        let kind = if (cpuid.ecx & 0b1) != 0 {
+            XStateKind::Supervisor
+        } else {
+            XStateKind::User
+        };
+
+        Self {
+            index,
+            size: cpuid.eax,
+            offset: cpuid.ebx,
+            kind,
+            align_64: (cpuid.ecx & 0b10) != 0,
+            xfd: (cpuid.ecx & 0b100) != 0,
+        }
+    }
+}
+
+/// XSAVE area layout of all state components supported by XCR0 and IA32_XSS, `CPUID.(EAX=0Dh)`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XsaveLayout {
+    /// Sub-leaf 0, EDX:EAX: Supported bits of XCR0
+    pub xcr0_mask: u64,
+    /// Sub-leaf 1, EDX:ECX: Supported bits of IA32_XSS
+    pub xss_mask: u64,
+    /// Sub-leaf 0, EBX: Size of the standard format for the features currently enabled in XCR0
+    pub enabled_size: u32,
+    /// Sub-leaf 0, ECX: Size of the standard format for all features supported by XCR0
+    pub standard_size: u32,
+    /// Sub-leaf 1, EBX: Size of the compacted format for the features currently enabled in XCR0 | IA32_XSS
+    pub enabled_compacted_size: u32,
+    /// Components 2 and above, in order of the index
+    pub components: Vec<XStateComponent>,
+}
+
+#[cfg(feature = "std")]
+impl XsaveLayout {
+    pub fn get() -> Option<Self> {
+        Self::get_from_source(&LiveCpuid)
+    }
+
+    pub fn get_from_source<S: CpuidSource>(src: &S) -> Option<Self> {
+        if src.cpuid(0x0, 0x0).eax < 0xD {
+            return None;
+        }
+
+        let [sub0, sub1] = [0x0, 0x1].map(|sub_leaf| src.cpuid(0xD, sub_leaf));
+        let xcr0_mask = ((sub0.edx as u64) << 32) | sub0.eax as u64;
+        let xss_mask = ((sub1.edx as u64) << 32) | sub1.ecx as u64;
+
+        if xcr0_mask == 0 {
+            return None;
+        }
+
+        let components = (2..64u8)
+            .filter(|i| ((xcr0_mask | xss_mask) >> i) & 0b1 != 0)
+            .map(|i| XStateComponent::from_cpuid(i, &src.cpuid(0xD, i as u32)))
+            .collect();
+
+        Some(Self {
+            xcr0_mask,
+            xss_mask,
+            enabled_size: sub0.ebx,
+            standard_size: sub0.ecx,
+            enabled_compacted_size: sub1.ebx,
+            components,
+        })
+    }
+
+    /// Offset in the compacted format of the components in `mask`,
+    /// `None` if the component is not in `mask` or not supported
+    pub fn compacted_offset(&self, index: u8, mask: u64) -> Option<u32> {
+        if (mask >> index) & 0b1 == 0 {
+            return None;
+        }
+
+        let mut offset = XSAVE_LEGACY_HEADER_SIZE;
+
+        for c in self.components.iter().filter(|c| (mask >> c.index) & 0b1 != 0) {
+            if c.align_64 {
+                offset = (offset + 63) & !63;
+            }
+
+            if c.index == index {
+                return Some(offset);
+            }
+
+            offset += c.size;
+        }
+
+        None
+    }
+
+    /// Size of the compacted format for the components in `mask`
+    pub fn compacted_size(&self, mask: u64) -> u32 {
+        self.components
+            .iter()
+            .filter(|c| (mask >> c.index) & 0b1 != 0)
+            .fold(XSAVE_LEGACY_HEADER_SIZE, |offset, c| {
+                let offset = if c.align_64 { (offset + 63) & !63 } else { offset };
+
+                offset + c.size
+            })
+    }
+
+    /// Size of the compacted format for all components supported by XCR0 and IA32_XSS
+    pub fn max_compacted_size(&self) -> u32 {
+        self.compacted_size(self.xcr0_mask | self.xss_mask)
+    }
+}
+
+#[test]
+fn test_xsave_layout() {
+    use crate::CpuidTable;
+
+    /* Sapphire Rapids */
+    let src = CpuidTable::from_regs(&[
+        (0x0, 0x0, [0x1F, 0x756E6547, 0x6C65746E, 0x49656E69]),
+        (0xD, 0x0, [0x000602E7, 0x00002B00, 0x00002B00, 0x0]),
+        (0xD, 0x1, [0x0000001F, 0x00002A00, 0x00001800, 0x0]),
+        (0xD, 0x2, [0x00000100, 0x00000240, 0x0, 0x0]),
+        (0xD, 0x5, [0x00000040, 0x00000440, 0x0, 0x0]),
+        (0xD, 0x6, [0x00000200, 0x00000480, 0x0, 0x0]),
+        (0xD, 0x7, [0x00000400, 0x00000680, 0x0, 0x0]),
+        (0xD, 0x9, [0x00000008, 0x00000A80, 0x0, 0x0]),
+        (0xD, 0xB, [0x00000010, 0x0, 0x1, 0x0]),
+        (0xD, 0xC, [0x00000018, 0x0, 0x1, 0x0]),
+        (0xD, 0x11, [0x00000040, 0x00000AC0, 0x2, 0x0]),
+        (0xD, 0x12, [0x00002000, 0x00000B00, 0x6, 0x0]),
+    ]);
+
+    let layout = XsaveLayout::get_from_source(&src).unwrap();
+    let indexes: Vec<u8> = layout.components.iter().map(|c| c.index).collect();
+
+    assert_eq!(indexes, [2, 5, 6, 7, 9, 11, 12, 17, 18]);
+    assert_eq!(layout.components[5].kind, XStateKind::Supervisor);
+    assert!(layout.components[8].align_64 && layout.components[8].xfd);
+    assert_eq!(layout.compacted_offset(18, layout.xcr0_mask | layout.xss_mask), Some(0xA00));
+    assert_eq!(layout.max_compacted_size(), layout.enabled_compacted_size);
+
+    /* CPUID Ryzen 5 5600G */
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt");
+    let layout = XsaveLayout::get_from_source(&CpuidTable::from_raw_dump(dump)).unwrap();
+    let indexes: Vec<u8> = layout.components.iter().map(|c| c.index).collect();
+
+    assert_eq!(indexes, [2, 9, 11, 12]);
+    assert_eq!((layout.xcr0_mask, layout.xss_mask), (0x207, 0x1800));
+    assert_eq!((layout.components[1].offset, layout.components[1].size), (0x980, 8));
+    assert_eq!(layout.standard_size, 0x988);
+}
@@ -1,7 +1,9 @@
 use std::io;
 use crate::TOTAL_WIDTH;
-use crate::load_aida64_log;
-use crate::{cpuid, CpuidDump, dump_all_threads, leaf_pool, CpuVendor, RawCpuid};
+use crate::{dump_diff, json_dump, load_aida64_log, load_cpuid_r, load_dump, load_raw_dump};
+use crate::{cpuid, disp_snapshots, dump_all_threads, CpuVendor, RawCpuid, RawCpuidFmt};
+use crate::Snapshot;
+use libcpuid_dump::leaf_pool;
 
 const LEAF_HEAD: &str = "       [Leaf.Sub]";
 const LEAF_LINE: &str = unsafe { std::str::from_utf8_unchecked(&[b'='; LEAF_HEAD.len()]) };
@@ -57,6 +59,8 @@ fn help_msg() {
         "\n",
         "USAGE:\n",
         "    cargo run -- [options ..] or <cpuid_dump> [options ..]\n",
+        "    <cpuid_dump> diff <path/filename A> <path/filename B>\n",
+        "        Compare feature flags and decoded fields of two dumps (any format of \"--load\").\n",
         "\n",
         "FLAGS:\n",
         "    -a, -all\n",
@@ -67,12 +71,17 @@ fn help_msg() {
         "        Display binary result.\n",
         "    -c, -compat\n",
         "        Display the same format as `cpuid -r` (cpuid by Todd Allen)\n",
+        "    -json\n",
+        "        Output raw and decoded results in JSON, \"-no-diff\" is implied.\n",
         "    -full\n",
         "        Combine \"-disp-zero\" and \"-no-diff\"\n",
         "    -disp-zero\n",
         "        Display result even if E[ABCD]X are zero.\n",
         "    -no-diff\n",
         "        Do not omit diff when all threads execution\n",
+        "    -cpuid-dev\n",
+        "        Read CPUID of all threads from \"/dev/cpu/N/cpuid\" (Linux) instead of pinning the thread.\n",
+        "        Fall back to pinning if the device is not available.\n",
         "\n",
         "OPTIONS:\n",
         "    --l <u32>, --leaf <u32>\n",
@@ -83,7 +92,17 @@ fn help_msg() {
         "    --s <path/filename>, --save <path/filename>\n",
         "        Save dump result to text file.\n",
         "        If there is no path/filename argument, will be used \"./<processor_name>\".\n",
+        "    --load <path/filename>\n",
+        "        Load and display the CPUID dump, the format is detected from the file.\n",
+        "        (JSON, AIDA64, InstLatX64/linux-hardware.org, \"cpuid -r\", \"-raw\"/\"-parse\"/\"-bin\")\n",
         "    --aida64 <path/filename>\n",
+        "        Load and display the CPUID dump from AIDA64 log.\n",
+        "    --load-json <path/filename>\n",
+        "        Load and display the CPUID dump from JSON output by \"-json\".\n",
+        "    --load-raw <path/filename>\n",
+        "        Load and display the CPUID dump output by \"-raw\", \"-parse\", \"-bin\" or \"--save\".\n",
+        "    --load-cpuid-r <path/filename>\n",
+        "        Load and display the CPUID dump output by \"cpuid -r\" (cpuid by Todd Allen).\n",
     );
 
     println!("{MSG}")
@@ -96,6 +115,7 @@ pub enum DumpFormat {
     Parse,
     CompatCpuid,
     Debug,
+    Json,
 }
 
 impl DumpFormat {
@@ -103,18 +123,21 @@ impl DumpFormat {
         match self {
             Self::Binary => bin_head(),
             Self::Debug |
-            Self::CompatCpuid => "".to_string(),
+            Self::CompatCpuid |
+            Self::Json => "".to_string(),
             _ => hex_head(),
         }
     }
 
-    pub fn rawcpuid_fmt_func(&self) -> fn(&RawCpuid, &CpuVendor) -> String {
+    /// `None` for JSON, which is output per Snapshot, see json_dump.rs
+    pub fn rawcpuid_fmt_func(&self) -> Option<fn(&RawCpuid, &CpuVendor) -> String> {
         match self {
-            Self::Raw => RawCpuid::raw_fmt,
-            Self::Binary => RawCpuid::bin_fmt,
-            Self::Parse => RawCpuid::parse_fmt,
-            Self::CompatCpuid => RawCpuid::compat_fmt,
-            Self::Debug => RawCpuid::debug_fmt,
+            Self::Raw => Some(RawCpuid::raw_fmt),
+            Self::Binary => Some(RawCpuid::bin_fmt),
+            Self::Parse => Some(RawCpuid::parse_fmt),
+            Self::CompatCpuid => Some(RawCpuid::compat_fmt),
+            Self::Debug => Some(RawCpuid::debug_fmt),
+            Self::Json => None,
         }
     }
 }
@@ -127,7 +150,13 @@ pub struct MainOpt {
     pub leaf: Option<(u32, u32)>,
     pub skip_zero: bool,
     pub diff: bool,
+    pub cpuid_dev: bool,
     pub load_aida64: Option<String>,
+    pub load: Option<String>,
+    pub load_json: Option<String>,
+    pub load_raw: Option<String>,
+    pub load_cpuid_r: Option<String>,
+    pub diff_path: Option<[String; 2]>,
 }
 
 impl Default for MainOpt {
@@ -139,7 +168,13 @@ impl Default for MainOpt {
             leaf: None,
             skip_zero: true,
             diff: true,
+            cpuid_dev: false,
             load_aida64: None,
+            load: None,
+            load_json: None,
+            load_raw: None,
+            load_cpuid_r: None,
+            diff_path: None,
         }
     }
 }
@@ -162,6 +197,18 @@ impl MainOpt {
 
         let args: Vec<String> = std::env::args().collect();
 
+        /* cpuid_dump diff <A> <B> */
+        if args.get(1).map(|s| s.as_str()) == Some("diff") {
+            let (Some(a), Some(b)) = (args.get(2), args.get(3)) else {
+                eprintln!("missing argument <path/filename A> <path/filename B> to \"diff\"");
+                std::process::exit(1);
+            };
+
+            opt.diff_path = Some([a.clone(), b.clone()]);
+
+            return opt;
+        }
+
         for (idx, arg) in args.iter().enumerate() {
             if skip {
                 skip = false;
@@ -209,6 +256,38 @@ impl MainOpt {
                         std::process::exit(1);
                     }
                 },
+                "load" => {
+                    opt.load = args.get(idx+1).cloned();
+
+                    if opt.load.is_none() {
+                        eprintln!("missing argument <path/filename> to \"--load\"");
+                        std::process::exit(1);
+                    }
+                },
+                "load-json" => {
+                    opt.load_json = args.get(idx+1).cloned();
+
+                    if opt.load_json.is_none() {
+                        eprintln!("missing argument <path/filename> to \"--load-json\"");
+                        std::process::exit(1);
+                    }
+                },
+                "load-raw" => {
+                    opt.load_raw = args.get(idx+1).cloned();
+
+                    if opt.load_raw.is_none() {
+                        eprintln!("missing argument <path/filename> to \"--load-raw\"");
+                        std::process::exit(1);
+                    }
+                },
+                "load-cpuid-r" => {
+                    opt.load_cpuid_r = args.get(idx+1).cloned();
+
+                    if opt.load_cpuid_r.is_none() {
+                        eprintln!("missing argument <path/filename> to \"--load-cpuid-r\"");
+                        std::process::exit(1);
+                    }
+                },
                 "leaf" => {
                     opt.skip_zero = false;
                     opt.diff = false;
@@ -240,6 +319,10 @@ impl MainOpt {
                 "debug" => {
                     opt.fmt = DumpFormat::Debug
                 },
+                "json" => {
+                    opt.fmt = DumpFormat::Json;
+                    opt.diff = false;
+                },
                 "h" | "help" => {
                     help_msg();
                     std::process::exit(0);
@@ -250,6 +333,9 @@ impl MainOpt {
                 "no-diff" => {
                     opt.diff = false;
                 },
+                "cpuid-dev" => {
+                    opt.cpuid_dev = true;
+                },
                 "full" => {
                     opt.skip_zero = false;
                     opt.diff = false;
@@ -269,20 +355,20 @@ impl MainOpt {
         let leaf_pool = leaf_pool();
 
         if self.dump_all {
-            return dump_all_threads(&leaf_pool, self.skip_zero, self.fmt, self.diff).into_bytes();
+            return dump_all_threads(&leaf_pool, self.skip_zero, self.fmt, self.diff, self.cpuid_dev).into_bytes();
         }
 
-        let cpuid_dump = CpuidDump::new(&leaf_pool, self.skip_zero);
+        let snapshot = Snapshot::new(&leaf_pool, self.skip_zero);
 
-        cpuid_dump.top_disp(self.fmt).into_bytes()
+        disp_snapshots(&mut [snapshot], self.fmt, self.diff).into_bytes()
     }
 
     pub fn only_leaf(&self, leaf: u32, sub_leaf: u32) -> io::Result<()> {
         let tmp = if self.dump_all {
-            dump_all_threads(&[(leaf, sub_leaf)], self.skip_zero, self.fmt, self.diff)
+            dump_all_threads(&[(leaf, sub_leaf)], self.skip_zero, self.fmt, self.diff, self.cpuid_dev)
         } else {
-            let cpuid_dump = CpuidDump::new(&[(leaf, sub_leaf)], self.skip_zero);
-            cpuid_dump.top_disp(self.fmt)
+            let snapshot = Snapshot::new(&[(leaf, sub_leaf)], self.skip_zero);
+            disp_snapshots(&mut [snapshot], self.fmt, self.diff)
         };
 
         dump_write(&tmp.into_bytes())?;
@@ -304,27 +390,63 @@ impl MainOpt {
         Ok(())
     }
 
-    pub fn load_aida64(&self, path: &String) -> io::Result<()> {
+    pub fn load(&self, path: &str) -> io::Result<()> {
+        let dump = std::fs::read_to_string(path)?;
+        let mut snapshots = load_dump::parse_dump(&dump)?;
+
+        dump_write(&disp_snapshots(&mut snapshots, self.fmt, self.diff).into_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn diff_files(&self, [a, b]: &[String; 2]) -> io::Result<()> {
+        let diff = dump_diff::diff_files(a, b)?;
+
+        dump_write(&diff.into_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn load_aida64(&self, path: &str) -> io::Result<()> {
         let log = std::fs::read_to_string(path)?;
-        let mut vec_cpuid_dump = load_aida64_log::parse_aida64(&log);
-        let mut cpuid_dump_iter = vec_cpuid_dump.iter_mut();
-        let first = cpuid_dump_iter.next().unwrap();
-
-        let s = first.top_disp(self.fmt);
-        let ss: String = cpuid_dump_iter.map(|cpuid_dump| {
-            if self.diff {
-                let mut first_rawcpuid_pool = first.rawcpuid_pool.iter();
-
-                cpuid_dump.rawcpuid_pool.retain(|sub| {
-                    let Some(first) = first_rawcpuid_pool.next() else { return false };
-                    first != sub
-                });
-            }
+        let mut snapshots = load_aida64_log::parse_aida64(&log);
+
+        dump_write(&disp_snapshots(&mut snapshots, self.fmt, self.diff).into_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn load_json(&self, path: &str) -> io::Result<()> {
+        let json = std::fs::read_to_string(path)?;
+        let mut snapshots = json_dump::parse_json(&json)?;
+
+        dump_write(&disp_snapshots(&mut snapshots, self.fmt, self.diff).into_bytes())?;
 
-            cpuid_dump.disp(self.fmt)
-        }).collect();
+        Ok(())
+    }
+
+    pub fn load_raw(&self, path: &str) -> io::Result<()> {
+        let dump = std::fs::read_to_string(path)?;
+        let mut snapshots = load_raw_dump::parse_raw_dump(&dump);
+
+        if snapshots.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "no CPUID results in the dump"));
+        }
+
+        dump_write(&disp_snapshots(&mut snapshots, self.fmt, self.diff).into_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn load_cpuid_r(&self, path: &str) -> io::Result<()> {
+        let log = std::fs::read_to_string(path)?;
+        let mut snapshots = load_cpuid_r::parse_cpuid_r(&log);
+
+        if snapshots.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "no CPUID results in the dump"));
+        }
 
-        dump_write(&format!("{s}{ss}").into_bytes())?;
+        dump_write(&disp_snapshots(&mut snapshots, self.fmt, self.diff).into_bytes())?;
 
         Ok(())
     }
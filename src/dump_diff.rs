@@ -0,0 +1,190 @@
+use std::io;
+use crate::{load_dump, Snapshot};
+use libcpuid_dump::{
+    util,
+    AddressSize,
+    CacheType,
+    CpuidSource,
+    FamModStep,
+    FeatureSet,
+    MicroArchLevel,
+    ProcInfo,
+    ProcName,
+};
+
+fn cache_fields(snapshot: &Snapshot) -> Vec<(String, String)> {
+    snapshot.cache_props().map(|cache| {
+        let cache_type = match cache.cache_type {
+            CacheType::Data => "d",
+            CacheType::Instruction => "i",
+            _ => "",
+        };
+
+        (
+            format!("L{}{cache_type} Cache", cache.level),
+            format!(
+                "{} {}, {}-way, {}B line, shared by {} threads{}",
+                cache.size_in_the_unit(),
+                cache.size_unit,
+                cache.way,
+                cache.line_size,
+                cache.share_thread,
+                if cache.inclusive { ", inclusive" } else { "" },
+            ),
+        )
+    }).collect()
+}
+
+/* Decoded fields of a thread to compare, the field name and value */
+fn decoded_fields(snapshot: &Snapshot) -> Vec<(String, String)> {
+    let fms = FamModStep::get_from_source(snapshot);
+    let proc_info = ProcInfo::from_fms(&fms, &snapshot.cpu_vendor);
+    let addr_size = AddressSize::get_from_source(snapshot);
+    let opt_to_string = |v: Option<u32>| v.map_or("-".to_string(), |v| v.to_string());
+
+    let mut fields = vec![
+        ("Vendor".to_string(), snapshot.cpu_vendor.to_string()),
+        ("ProcName".to_string(), ProcName::get_trim_name_from_source(snapshot)),
+        /* Processor signature, the value used to select the microcode */
+        ("Signature".to_string(), format!("{:#010X}", snapshot.cpuid(0x1, 0x0).eax)),
+        ("FMS".to_string(), format!("F: {:#X}, M: {:#X}, S: {:#X}", fms.syn_fam, fms.syn_mod, fms.step)),
+        ("Codename".to_string(), proc_info.codename.to_string()),
+        ("Arch".to_string(), proc_info.archname.to_string()),
+        ("StepInfo".to_string(), proc_info.step_info.to_string()),
+        ("ProcessNode".to_string(), proc_info.node.map_or("-".to_string(), |node| node.to_string())),
+        ("MicroArchLevel".to_string(), format!("{:?}", MicroArchLevel::check_from_source(snapshot))),
+        ("PhysicalAddressSize".to_string(), format!("{}-bit", addr_size.physical)),
+        ("VirtualAddressSize".to_string(), format!("{}-bit", addr_size.virtual_)),
+        ("GuestPhysicalAddressSize".to_string(), format!("{}-bit", addr_size.guest_physical_size())),
+        ("ThreadsPerCore".to_string(), opt_to_string(util::get_threads_per_core_from_source(snapshot))),
+        (
+            "LogicalProcessors".to_string(),
+            opt_to_string(util::get_total_logical_processor_from_source(snapshot)),
+        ),
+    ];
+
+    fields.extend(cache_fields(snapshot));
+
+    fields
+}
+
+/* Added and removed feature flags */
+fn diff_features(a: &Snapshot, b: &Snapshot) -> Vec<String> {
+    let [ftr_a, ftr_b] = [a, b].map(FeatureSet::from_source);
+    let [added, removed] = [ftr_b - ftr_a, ftr_a - ftr_b];
+
+    added.iter().map(|ftr| format!("  + {ftr}\n"))
+        .chain(removed.iter().map(|ftr| format!("  - {ftr}\n")))
+        .collect()
+}
+
+/* Changed decoded fields */
+fn diff_fields(fields_a: &[(String, String)], fields_b: &[(String, String)]) -> Vec<String> {
+    let mut changed = Vec::new();
+
+    for (name, val_a) in fields_a {
+        let val_b = fields_b.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str());
+
+        if Some(val_a.as_str()) != val_b {
+            changed.push(format!("  {name}: {val_a} -> {}\n", val_b.unwrap_or("-")));
+        }
+    }
+    for (name, val_b) in fields_b {
+        if !fields_a.iter().any(|(n, _)| n == name) {
+            changed.push(format!("  {name}: - -> {val_b}\n"));
+        }
+    }
+
+    changed
+}
+
+/* Compare the first thread of each dump, feature flags and decoded fields,
+   then the other threads in the order of the dump, only the differences not reported for the first thread */
+fn diff_snapshots(a: &[Snapshot], b: &[Snapshot]) -> String {
+    let mut out = String::new();
+
+    let added_removed = diff_features(&a[0], &b[0]);
+
+    if !added_removed.is_empty() {
+        out.push_str("[Feature]\n");
+        out.push_str(&added_removed.concat());
+    }
+
+    let mut changed = diff_fields(&decoded_fields(&a[0]), &decoded_fields(&b[0]));
+
+    if a.len() != b.len() {
+        changed.push(format!("  ThreadsInDump: {} -> {}\n", a.len(), b.len()));
+    }
+
+    if !changed.is_empty() {
+        out.push_str("[Decoded]\n");
+        out.push_str(&changed.concat());
+    }
+
+    for (i, (thread_a, thread_b)) in a.iter().zip(b.iter()).enumerate().skip(1) {
+        let mut diff = [
+            diff_features(thread_a, thread_b),
+            diff_fields(&decoded_fields(thread_a), &decoded_fields(thread_b)),
+        ].concat();
+
+        diff.retain(|line| !added_removed.contains(line) && !changed.contains(line));
+
+        if !diff.is_empty() {
+            out.push_str(&format!("[Thread {:03}]\n", thread_a.thread_id.unwrap_or(i)));
+            out.push_str(&diff.concat());
+        }
+    }
+
+    if out.is_empty() {
+        out.push_str("No differences\n");
+    }
+
+    out
+}
+
+pub(crate) fn diff_files(path_a: &str, path_b: &str) -> io::Result<String> {
+    let [a, b] = [path_a, path_b].map(|path| -> io::Result<Vec<Snapshot>> {
+        load_dump::parse_dump(&std::fs::read_to_string(path)?)
+    });
+    let [a, b] = [a?, b?];
+
+    if a.is_empty() || b.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "no CPUID results in the dump"));
+    }
+
+    Ok(format!("--- {path_a}\n+++ {path_b}\n{}", diff_snapshots(&a, &b)))
+}
+
+#[test]
+fn test_diff_dump_result() {
+    use crate::{args::DumpFormat, disp_snapshots, load_raw_dump::load_fixture};
+
+    let ryzen_2600 = load_dump::parse_dump(include_str!("../dump_result/AMD_Ryzen_5_2600_00800F82h.txt")).unwrap();
+    let ryzen_5600g = load_fixture();
+
+    /* the same dump in the other format */
+    for snapshots in [&ryzen_2600, &ryzen_5600g] {
+        let reloaded = load_dump::parse_dump(&disp_snapshots(&mut snapshots.clone(), DumpFormat::Raw, true)).unwrap();
+
+        assert_eq!(diff_snapshots(snapshots, &reloaded), "No differences\n");
+    }
+
+    let bin = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00_bin.txt");
+    assert_eq!(diff_snapshots(&ryzen_5600g, &load_dump::parse_dump(bin).unwrap()), "No differences\n");
+
+    /* Zen+ to Zen 3, both 6 cores and 12 threads */
+    let diff = diff_snapshots(&ryzen_2600, &ryzen_5600g);
+
+    assert!(diff.contains("  + VAES\n") && diff.contains("  Arch: AMD Zen+ -> AMD Zen 3\n"));
+    assert!(!diff.contains("ThreadsPerCore") && !diff.contains("LogicalProcessors") && !diff.contains("ThreadsInDump"));
+    /* the APIC ID is not compared, and the other differences are the same as the first thread */
+    assert!(!diff.contains("[Thread"));
+
+    /* a thread is changed */
+    let mut changed = ryzen_5600g.clone();
+    let leaf_07h = changed[5].rawcpuid_pool.iter_mut().find(|r| r.leaf == 0x7 && r.sub_leaf == 0x0).unwrap();
+    /* VAES: ECX[9] */
+    leaf_07h.result.ecx &= !(1 << 9);
+
+    assert_eq!(diff_snapshots(&ryzen_5600g, &changed), "[Thread 005]\n  - VAES\n");
+}
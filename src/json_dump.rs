@@ -0,0 +1,184 @@
+use std::io;
+use std::convert::TryFrom;
+use serde_json::{json, Value};
+use crate::CpuidResult;
+use libcpuid_dump::{
+    util,
+    ConfidentialComputing,
+    FamModStep,
+    FeatureSet,
+    MicroArchLevel,
+    ProcInfo,
+    ProcName,
+    RawCpuid,
+    Snapshot,
+    TopoId,
+};
+
+/* The stable names of `CpuFeature`, not the display labels of the parse format */
+fn feature_list(snapshot: &Snapshot) -> Vec<&'static str> {
+    FeatureSet::from_source(snapshot).iter().map(|ftr| ftr.name()).collect()
+}
+
+fn cache_list(snapshot: &Snapshot) -> Vec<Value> {
+    snapshot.cache_props().map(|cache| {
+        json!({
+            "level": cache.level,
+            "type": cache.cache_type.to_string(),
+            "size": cache.size,
+            "way": cache.way,
+            "set": cache.set,
+            "line_size": cache.line_size,
+            "share_thread": cache.share_thread,
+            "inclusive": cache.inclusive,
+        })
+    }).collect()
+}
+
+fn confidential_computing(snapshot: &Snapshot) -> Value {
+    let cc = ConfidentialComputing::get_from_source(snapshot);
+    let pconfig_targets: Vec<String> = cc.pconfig_targets.iter().map(|target| target.to_string()).collect();
+
+    json!({
+        "sme": cc.sme,
+        "sev": cc.sev,
+        "sev_es": cc.sev_es,
+        "sev_snp": cc.sev_snp,
+        "c_bit": cc.c_bit,
+        "num_vmpl": cc.num_vmpl,
+        "tme": cc.tme,
+        "mktme": cc.mktme(),
+        "pconfig_targets": pconfig_targets,
+        "key_locker": cc.key_locker.map(|kl| json!({ "aeskle": kl.aeskle, "wide": kl.wide })),
+        "guest": cc.guest.map(|guest| guest.to_string()),
+        "sev_exposed_to_guest": cc.sev_exposed_to_guest,
+    })
+}
+
+fn decoded(snapshot: &Snapshot) -> Value {
+    let fms = FamModStep::get_from_source(snapshot);
+    let proc_info = ProcInfo::from_fms(&fms, &snapshot.cpu_vendor);
+
+    json!({
+        "vendor": snapshot.cpu_vendor.to_string(),
+        "proc_name": ProcName::get_trim_name_from_source(snapshot),
+        "fms": {
+            "family": fms.syn_fam,
+            "model": fms.syn_mod,
+            "stepping": fms.step,
+        },
+        "codename": proc_info.codename.to_string(),
+        "micro_arch": proc_info.archname.to_string(),
+        "step_info": proc_info.step_info.to_string(),
+        "process_node": proc_info.node.map(|node| node.to_string()),
+        "micro_arch_level": format!("{:?}", MicroArchLevel::check_from_source(snapshot)),
+        "topology": {
+            "threads_per_core": util::get_threads_per_core_from_source(snapshot),
+            "logical_processors": util::get_total_logical_processor_from_source(snapshot),
+        },
+        "caches": cache_list(snapshot),
+        "features": feature_list(snapshot),
+        "confidential_computing": confidential_computing(snapshot),
+    })
+}
+
+fn snapshot_to_json(snapshot: &Snapshot) -> Value {
+    let topo_id = snapshot.topo_id.map(|topo| {
+        let TopoId { pkg_id, core_id, smt_id, x2apic_id } = topo;
+
+        json!({
+            "pkg_id": pkg_id,
+            "core_id": core_id,
+            "smt_id": smt_id,
+            "x2apic_id": x2apic_id,
+        })
+    });
+    let raw: Vec<Value> = snapshot.rawcpuid_pool.iter().map(|rawcpuid| {
+        let CpuidResult { eax, ebx, ecx, edx } = rawcpuid.result;
+
+        json!({
+            "leaf": rawcpuid.leaf,
+            "sub_leaf": rawcpuid.sub_leaf,
+            "eax": eax,
+            "ebx": ebx,
+            "ecx": ecx,
+            "edx": edx,
+        })
+    }).collect();
+
+    json!({
+        "thread_id": snapshot.thread_id,
+        "topo_id": topo_id,
+        "raw": raw,
+        "decoded": decoded(snapshot),
+    })
+}
+
+pub(crate) fn snapshots_to_json(snapshots: &[Snapshot]) -> String {
+    let threads: Vec<Value> = snapshots.iter().map(snapshot_to_json).collect();
+    let json = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "threads": threads,
+    });
+
+    format!("{}\n", serde_json::to_string_pretty(&json).unwrap())
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn get_u32(value: &Value, key: &str) -> io::Result<u32> {
+    value[key]
+        .as_u64()
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| invalid_data(&format!("invalid or missing \"{key}\"")))
+}
+
+fn snapshot_from_json(thread: &Value) -> io::Result<Snapshot> {
+    let rawcpuid_pool = thread["raw"]
+        .as_array()
+        .ok_or_else(|| invalid_data("missing \"raw\""))?
+        .iter()
+        .map(|raw| -> io::Result<RawCpuid> {
+            Ok(RawCpuid {
+                leaf: get_u32(raw, "leaf")?,
+                sub_leaf: get_u32(raw, "sub_leaf")?,
+                result: CpuidResult {
+                    eax: get_u32(raw, "eax")?,
+                    ebx: get_u32(raw, "ebx")?,
+                    ecx: get_u32(raw, "ecx")?,
+                    edx: get_u32(raw, "edx")?,
+                },
+            })
+        })
+        .collect::<io::Result<Vec<RawCpuid>>>()?;
+
+    let topo_id = match &thread["topo_id"] {
+        Value::Null => None,
+        topo => Some(TopoId {
+            pkg_id: get_u32(topo, "pkg_id")?,
+            core_id: get_u32(topo, "core_id")?,
+            smt_id: get_u32(topo, "smt_id")?,
+            x2apic_id: get_u32(topo, "x2apic_id")?,
+        }),
+    };
+    let thread_id = thread["thread_id"].as_u64().map(|id| id as usize);
+
+    /* The decoded fields are not used, re-decode from the raw results */
+    let mut snapshot = Snapshot::from_rawcpuid_pool(rawcpuid_pool, thread_id);
+    snapshot.topo_id = topo_id;
+
+    Ok(snapshot)
+}
+
+pub(crate) fn parse_json(json: &str) -> io::Result<Vec<Snapshot>> {
+    let json: Value = serde_json::from_str(json)?;
+
+    json["threads"]
+        .as_array()
+        .ok_or_else(|| invalid_data("missing \"threads\""))?
+        .iter()
+        .map(snapshot_from_json)
+        .collect()
+}
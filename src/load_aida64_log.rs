@@ -1,13 +1,13 @@
-use crate::{CpuidDump, CpuidResult, CpuVendor, RawCpuid};
+use crate::{CpuidResult, CpuVendor, RawCpuid, Snapshot};
 use libcpuid_dump::{TopoLevelType, TopoId};
 
-pub(crate) fn parse_aida64(log: &String) -> Vec<CpuidDump> {
+pub(crate) fn parse_aida64(log: &str) -> Vec<Snapshot> {
     let mut rawcpuid_pool: Vec<RawCpuid> = Vec::new();
     let mut pre_leaf = u32::MAX;
     let mut sub_leaf = 0u32;
     let mut cpu_vendor: Option<CpuVendor> = None;
     let [mut smt_cpuid, mut core_cpuid]: [Option<CpuidResult>; 2] = [None, None];
-    let mut vec_cpuid_dump = Vec::new();
+    let mut snapshots = Vec::new();
 
     for line in log.lines().skip(1) {
         if line.starts_with("Group:") || line.starts_with("------[ CPUID Registers") {
@@ -18,7 +18,7 @@ pub(crate) fn parse_aida64(log: &String) -> Vec<CpuidDump> {
                 None
             };
 
-            vec_cpuid_dump.push(CpuidDump {
+            snapshots.push(Snapshot {
                 cpu_vendor,
                 rawcpuid_pool: rawcpuid_pool.clone(),
                 topo_id,
@@ -37,8 +37,10 @@ pub(crate) fn parse_aida64(log: &String) -> Vec<CpuidDump> {
         }
 
         let split: Vec<&str> = line.splitn(4, ' ').collect();
-        let Ok(leaf) = u32::from_str_radix(&split[1][..8], 16) else { continue };
-        let Some(result) = parse_reg(split[2]) else { continue };
+        let Some(leaf) = split.get(1)
+            .and_then(|s| s.get(..8))
+            .and_then(|s| u32::from_str_radix(s, 16).ok()) else { continue };
+        let Some(result) = split.get(2).and_then(|s| parse_reg(s)) else { continue };
 
         if pre_leaf == leaf {
             sub_leaf += 1;
@@ -71,10 +73,10 @@ pub(crate) fn parse_aida64(log: &String) -> Vec<CpuidDump> {
         rawcpuid_pool.push(rawcpuid);
     }
 
-    vec_cpuid_dump
+    snapshots
 }
 
-fn parse_reg(reg: &str) -> Option<CpuidResult> {
+pub(crate) fn parse_reg(reg: &str) -> Option<CpuidResult> {
     let result: Vec<u32> = reg
         .splitn(4, '-')
         .filter_map(|str_reg| u32::from_str_radix(str_reg, 16).ok())
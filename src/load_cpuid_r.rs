@@ -0,0 +1,125 @@
+use crate::{CpuidResult, RawCpuid, Snapshot, TopoId};
+use crate::load_raw_dump::{parse_head, snapshots_from_blocks};
+
+/* Load the dump of `cpuid -r` (cpuid by Todd Allen, http://www.etallen.com/cpuid.html):
+    CPU 0:
+       0x00000000 0x00: eax=0x00000010 ebx=0x68747541 ecx=0x444d4163 edx=0x69746e65
+       0x00000001 0x00: eax=0x00a50f00 ebx=0x000c0800 ecx=0x7ef8320b edx=0x178bfbff
+       ...
+    CPU 1:
+       ...
+
+   The output of "-c" is also loaded, the thread header is "[Pkg: 000, ..., Thread: 000]".
+*/
+pub(crate) fn parse_cpuid_r(log: &str) -> Vec<Snapshot> {
+    let mut blocks: Vec<(Option<TopoId>, Option<usize>, Vec<RawCpuid>)> = Vec::new();
+
+    for line in log.lines() {
+        let line = line.trim();
+
+        /* "CPU 0:", or "CPU:" for `cpuid -1 -r` */
+        if let Some(cpu) = line.strip_prefix("CPU").and_then(|s| s.strip_suffix(':')) {
+            blocks.push((None, cpu.trim().parse::<usize>().ok(), Vec::new()));
+            continue;
+        }
+
+        /* "[Pkg: 000, Core: 000, SMT: 000, x2APIC: 000, Thread: 000]" of "-c" output */
+        if let Some((topo_id, thread_id)) = parse_head(line) {
+            blocks.push((topo_id, thread_id, Vec::new()));
+            continue;
+        }
+
+        if let Some(rawcpuid) = parse_line(line) {
+            if blocks.is_empty() {
+                blocks.push((None, None, Vec::new()));
+            }
+
+            blocks.last_mut().unwrap().2.push(rawcpuid);
+        }
+    }
+
+    snapshots_from_blocks(blocks)
+}
+
+fn parse_hex(s: &str) -> Option<u32> {
+    u32::from_str_radix(s.strip_prefix("0x")?, 16).ok()
+}
+
+/* "0x00000007 0x00: eax=0x00000000 ebx=0x219c97a9 ecx=0x0040068c edx=0x00000010" */
+fn parse_line(line: &str) -> Option<RawCpuid> {
+    let (input, output) = line.split_once(':')?;
+
+    let mut input = input.split_whitespace();
+    let leaf = parse_hex(input.next()?)?;
+    let sub_leaf = parse_hex(input.next()?)?;
+
+    let mut result = CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 };
+    let mut count = 0;
+
+    for reg in output.split_whitespace() {
+        let (name, value) = reg.split_once('=')?;
+        let value = parse_hex(value)?;
+
+        match name {
+            "eax" => result.eax = value,
+            "ebx" => result.ebx = value,
+            "ecx" => result.ecx = value,
+            "edx" => result.edx = value,
+            _ => return None,
+        }
+
+        count += 1;
+    }
+
+    if count != 4 { return None }
+
+    Some(RawCpuid {
+        leaf,
+        sub_leaf,
+        result,
+    })
+}
+
+/* `cpuid -r` layout of the snapshots, for tests */
+#[cfg(test)]
+pub(crate) fn to_cpuid_r(snapshots: &[Snapshot]) -> String {
+    use crate::RawCpuidFmt;
+
+    snapshots.iter().enumerate().map(|(i, snapshot)| {
+        let results: String = snapshot.rawcpuid_pool
+            .iter()
+            .map(|rawcpuid| rawcpuid.compat_fmt(&snapshot.cpu_vendor))
+            .collect();
+
+        format!("CPU {i}:\n{results}")
+    }).collect()
+}
+
+#[test]
+fn test_load_cpuid_r() {
+    let snapshots = crate::load_raw_dump::load_fixture();
+    let loaded = parse_cpuid_r(&to_cpuid_r(&snapshots));
+
+    assert_eq!(loaded.len(), 12);
+
+    for (i, (a, b)) in snapshots.iter().zip(loaded.iter()).enumerate() {
+        assert_eq!(a.rawcpuid_pool, b.rawcpuid_pool);
+        assert_eq!(b.thread_id, Some(i));
+    }
+}
+
+#[test]
+fn test_load_compat_output() {
+    use crate::{args::DumpFormat, disp_snapshots, load_dump::parse_dump};
+
+    let snapshots = crate::load_raw_dump::load_fixture();
+    let compat = disp_snapshots(&mut snapshots.clone(), DumpFormat::CompatCpuid, true);
+    let loaded = parse_dump(&compat).unwrap();
+
+    assert_eq!(loaded.len(), 12);
+
+    for (a, b) in snapshots.iter().zip(loaded.iter()) {
+        assert_eq!(a.rawcpuid_pool, b.rawcpuid_pool);
+        assert_eq!((a.thread_id, a.topo_id), (b.thread_id, b.topo_id));
+    }
+}
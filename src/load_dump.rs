@@ -0,0 +1,58 @@
+use std::io;
+use crate::Snapshot;
+use crate::{json_dump, load_aida64_log, load_cpuid_r, load_instlat, load_raw_dump};
+
+/* Detect the format of the dump and load it.
+   Text formats are tried in order, AIDA64 log also matches the InstLatX64 format. */
+pub(crate) fn parse_dump(dump: &str) -> io::Result<Vec<Snapshot>> {
+    if dump.trim_start().starts_with('{') {
+        return json_dump::parse_json(dump);
+    }
+
+    let parsers: [fn(&str) -> Vec<Snapshot>; 4] = [
+        load_aida64_log::parse_aida64,
+        load_instlat::parse_instlat,
+        load_cpuid_r::parse_cpuid_r,
+        load_raw_dump::parse_raw_dump,
+    ];
+
+    parsers
+        .iter()
+        .map(|parse| parse(dump))
+        .find(|snapshots| !snapshots.is_empty())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unknown format of the dump"))
+}
+
+#[test]
+fn test_detect_format() {
+    use crate::{args::DumpFormat, disp_snapshots, json_dump, load_cpuid_r, load_instlat, load_raw_dump};
+
+    let snapshots = load_raw_dump::load_fixture();
+    let disp = |dump_fmt: DumpFormat| disp_snapshots(&mut snapshots.clone(), dump_fmt, true);
+
+    /* the output of "-c" is also the `cpuid -r` layout, but with the thread header of "-raw" */
+    for (name, dump) in [
+        ("JSON", json_dump::snapshots_to_json(&snapshots)),
+        ("InstLatX64", load_instlat::to_instlat(&snapshots)),
+        ("cpuid -r", load_cpuid_r::to_cpuid_r(&snapshots)),
+        ("-c", disp(DumpFormat::CompatCpuid)),
+        ("-raw", disp(DumpFormat::Raw)),
+        ("-parse", disp(DumpFormat::Parse)),
+        ("-bin", disp(DumpFormat::Binary)),
+    ] {
+        let loaded = parse_dump(&dump).unwrap();
+
+        assert_eq!(loaded.len(), snapshots.len(), "{name}");
+
+        for (a, b) in snapshots.iter().zip(loaded.iter()) {
+            assert_eq!(a.rawcpuid_pool, b.rawcpuid_pool, "{name}");
+            assert_eq!(a.thread_id, b.thread_id, "{name}");
+        }
+    }
+
+    /* old layout of "--save" */
+    let old = parse_dump(include_str!("../dump_result/AMD_Ryzen_5_2600_00800F82h.txt")).unwrap();
+    assert_eq!(old.len(), 12);
+
+    assert!(parse_dump("Lorem ipsum\n").is_err());
+}
@@ -0,0 +1,143 @@
+use crate::{CpuidResult, RawCpuid, Snapshot};
+use crate::load_aida64_log::parse_reg;
+
+/* Load the CPUID dump of InstLatX64 (http://instlatx64.atw.hu/) and linux-hardware.org:
+    CPU#001 AffMask: 0x0000000000000001
+    ...
+    CPUID 00000000: 0000000D-68747541-444D4163-69746E65 [AuthenticAMD]
+    CPUID 00000001: 00800F82-000C0800-7ED8320B-178BFBFF
+    CPUID 00000004: 1C004121-01C0003F-0000003F-00000000 [SL 00]
+    ...
+    CPU#002 AffMask: 0x0000000000000002
+    ...
+
+   or "------[ Logical CPU #0 ]------" for the thread header.
+   Sub-leaf is "[SL xx]", or counted from the repeated Leaf (same as AIDA64 log).
+*/
+pub(crate) fn parse_instlat(log: &str) -> Vec<Snapshot> {
+    let mut rawcpuid_pool: Vec<RawCpuid> = Vec::new();
+    let mut thread_id: Option<usize> = None;
+    let mut pre_leaf = u32::MAX;
+    let mut snapshots = Vec::new();
+
+    for line in log.lines() {
+        let line = line.trim();
+
+        if line == "------[ All CPUs ]------" {
+            break;
+        }
+
+        if let Some(cpu) = parse_cpu_head(line) {
+            if !rawcpuid_pool.is_empty() {
+                snapshots.push(Snapshot::from_rawcpuid_pool(rawcpuid_pool, thread_id));
+                rawcpuid_pool = Vec::new();
+            }
+
+            thread_id = cpu;
+            pre_leaf = u32::MAX;
+            continue;
+        }
+
+        let Some((leaf, sl, result)) = parse_line(line) else { continue };
+        let sub_leaf = match sl {
+            Some(sl) => sl,
+            None if pre_leaf == leaf => rawcpuid_pool.last().map_or(0, |pre| pre.sub_leaf + 1),
+            None => 0,
+        };
+
+        pre_leaf = leaf;
+
+        rawcpuid_pool.push(RawCpuid {
+            leaf,
+            sub_leaf,
+            result,
+        });
+    }
+
+    if !rawcpuid_pool.is_empty() {
+        snapshots.push(Snapshot::from_rawcpuid_pool(rawcpuid_pool, thread_id));
+    }
+
+    snapshots
+}
+
+/* "CPU#001 AffMask: 0x0000000000000001", "------[ Logical CPU #0 ]------" */
+fn parse_cpu_head(line: &str) -> Option<Option<usize>> {
+    if let Some(head) = line.strip_prefix("CPU#") {
+        /* The number of InstLatX64 starts from 1, use the affinity mask if exists */
+        let aff_mask = head
+            .split_once("AffMask:")
+            .and_then(|(_, mask)| {
+                u64::from_str_radix(mask.trim().trim_start_matches("0x"), 16).ok()
+            })
+            .filter(|mask| *mask != 0)
+            .map(|mask| mask.trailing_zeros() as usize);
+
+        return Some(aff_mask);
+    }
+
+    let (_, cpu) = line.strip_prefix("------[")?.split_once("Logical CPU #")?;
+    let cpu: String = cpu.chars().take_while(|c| c.is_ascii_digit()).collect();
+
+    Some(cpu.parse::<usize>().ok())
+}
+
+/* "CPUID 00000004: 1C004121-01C0003F-0000003F-00000000 [SL 00]" */
+fn parse_line(line: &str) -> Option<(u32, Option<u32>, CpuidResult)> {
+    let mut split = line.strip_prefix("CPUID ")?.split_whitespace();
+    let leaf = u32::from_str_radix(split.next()?.strip_suffix(':')?, 16).ok()?;
+    let result = parse_reg(split.next()?)?;
+    let sub_leaf = line
+        .split_once("[SL ")
+        .and_then(|(_, sl)| u32::from_str_radix(sl.split(']').next()?.trim(), 16).ok());
+
+    Some((leaf, sub_leaf, result))
+}
+
+/* InstLatX64 layout of the snapshots with "[SL xx]", for tests */
+#[cfg(test)]
+pub(crate) fn to_instlat(snapshots: &[Snapshot]) -> String {
+    snapshots.iter().enumerate().map(|(i, snapshot)| {
+        let results: String = snapshot.rawcpuid_pool.iter().map(|rawcpuid| {
+            let CpuidResult { eax, ebx, ecx, edx } = rawcpuid.result;
+
+            format!(
+                "CPUID {:08X}: {eax:08X}-{ebx:08X}-{ecx:08X}-{edx:08X} [SL {:02X}]\n",
+                rawcpuid.leaf,
+                rawcpuid.sub_leaf,
+            )
+        }).collect();
+
+        format!("CPU#{:03} AffMask: 0x{:016X}\n{results}", i + 1, 1u64 << i)
+    }).collect()
+}
+
+#[test]
+fn test_load_instlat() {
+    let snapshots = crate::load_raw_dump::load_fixture();
+    let loaded = parse_instlat(&to_instlat(&snapshots));
+
+    assert_eq!(loaded.len(), 12);
+
+    for (i, (a, b)) in snapshots.iter().zip(loaded.iter()).enumerate() {
+        assert_eq!(a.rawcpuid_pool, b.rawcpuid_pool);
+        assert_eq!(b.thread_id, Some(i));
+    }
+
+    /* Without "[SL xx]", the sub-leaf is counted from the repeated Leaf */
+    let log = "\
+        ------[ Logical CPU #3 ]------\n\
+        CPUID 00000000: 00000010-68747541-444D4163-69746E65 [AuthenticAMD]\n\
+        CPUID 8000001D: 00004121-01C0003F-0000003F-00000000\n\
+        CPUID 8000001D: 00004122-01C0003F-0000003F-00000000\n\
+        CPUID 8000001E: 00000000-00000100-00000000-00000000\n\
+        ------[ All CPUs ]------\n\
+        CPUID 00000000: 00000000-00000000-00000000-00000000\n\
+    ";
+    let loaded = parse_instlat(log);
+    let leaves: Vec<(u32, u32)> = loaded[0].rawcpuid_pool.iter().map(|r| (r.leaf, r.sub_leaf)).collect();
+
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].thread_id, Some(3));
+    assert_eq!(leaves, [(0x0, 0x0), (0x8000_001D, 0x0), (0x8000_001D, 0x1), (0x8000_001E, 0x0)]);
+}
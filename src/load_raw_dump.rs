@@ -0,0 +1,233 @@
+use crate::{CpuidResult, RawCpuid, Snapshot, TopoId};
+use libcpuid_dump::CpuidTable;
+
+/* Load the dump output by "-raw", "-parse" or "--save":
+    [Pkg: 000, Core: 000, SMT: 000, x2APIC: 000, Thread: 000]
+           [Leaf.Sub]    [EAX]      [EBX]      [ECX]      [EDX]
+    ======================================================================
+      0x00000000 0x0:  0x00000010 0x68747541 0x444D4163 0x69746E65  [AuthenticAMD]
+      ...
+    [Pkg: 000, Core: 001, SMT: 000, x2APIC: 002, Thread: 001]
+      ... (only the results that differ from the first thread)
+
+   "-bin" output, EAX/EBX and ECX/EDX on two lines:
+      0x00000000 0x0:  00000000_00000000_00000000_00010000  01101000_01110100_01110101_01000001
+                       01000100_01001101_01000001_01100011  01101001_01110100_01101110_01100101
+
+   and the old layout:
+    Core ID: 0   / Thread: 0
+    CPUID Dump
+    ======================================================================
+     00000000h_x0: eax=0000000Dh ebx=68747541h ecx=444D4163h edx=69746E65h [AuthenticAMD]
+*/
+pub(crate) fn parse_raw_dump(dump: &str) -> Vec<Snapshot> {
+    let mut blocks: Vec<(Option<TopoId>, Option<usize>, Vec<RawCpuid>)> = Vec::new();
+    /* Leaf, Sub-leaf, EAX, EBX of "-bin" output, waiting for the ECX/EDX line */
+    let mut bin_head: Option<(u32, u32, u32, u32)> = None;
+
+    for line in dump.lines() {
+        if let Some((topo_id, thread_id)) = parse_head(line) {
+            blocks.push((topo_id, thread_id, Vec::new()));
+            continue;
+        }
+
+        if let Some(head) = parse_bin_head(line) {
+            bin_head = Some(head);
+            continue;
+        }
+
+        let rawcpuid = CpuidTable::parse_raw_line(line)
+            .map(|(leaf, sub_leaf, result)| RawCpuid { leaf, sub_leaf, result })
+            .or_else(|| parse_old_line(line))
+            .or_else(|| {
+                let (leaf, sub_leaf, eax, ebx) = bin_head.take()?;
+                let [ecx, edx] = parse_bin_pair(line)?;
+
+                Some(RawCpuid { leaf, sub_leaf, result: CpuidResult { eax, ebx, ecx, edx } })
+            });
+
+        if let Some(rawcpuid) = rawcpuid {
+            /* dump of a single thread without the header */
+            if blocks.is_empty() {
+                blocks.push((None, None, Vec::new()));
+            }
+
+            blocks.last_mut().unwrap().2.push(rawcpuid);
+        }
+    }
+
+    snapshots_from_blocks(blocks)
+}
+
+/* Blocks of (topology ID, thread ID, CPUID results) to Snapshots,
+   the threads after the first thread may only have the results that differ from it ("-no-diff" is not given) */
+pub(crate) fn snapshots_from_blocks(blocks: Vec<(Option<TopoId>, Option<usize>, Vec<RawCpuid>)>) -> Vec<Snapshot> {
+    let mut snapshots: Vec<Snapshot> = Vec::with_capacity(blocks.len());
+
+    for (topo_id, thread_id, rawcpuid_pool) in blocks {
+        /* A thread after the first thread without results is the same as the first thread */
+        if rawcpuid_pool.is_empty() && snapshots.is_empty() { continue }
+
+        let mut snapshot = Snapshot::from_rawcpuid_pool(rawcpuid_pool, thread_id);
+
+        if let Some(first) = snapshots.first() {
+            snapshot.restore_diff(first);
+            snapshot = Snapshot::from_rawcpuid_pool(snapshot.rawcpuid_pool, thread_id);
+        }
+
+        if topo_id.is_some() {
+            snapshot.topo_id = topo_id;
+        }
+
+        snapshots.push(snapshot);
+    }
+
+    snapshots
+}
+
+/* "[Pkg: 000, Core: 000, SMT: 000, x2APIC: 000, Thread: 000]", "[Thread: 000]",
+   or "Core ID: 0   / Thread: 0" of the old layout */
+pub(crate) fn parse_head(line: &str) -> Option<(Option<TopoId>, Option<usize>)> {
+    if let Some(old) = line.strip_prefix("Core ID:") {
+        let (_, thread_id) = old.split_once("Thread:")?;
+
+        return Some((None, thread_id.trim().parse::<usize>().ok()));
+    }
+
+    let head = line.strip_prefix('[')?.trim_end().strip_suffix(']')?;
+    let [mut pkg_id, mut core_id, mut smt_id, mut x2apic_id] = [None; 4];
+    let mut thread_id = None;
+
+    for field in head.split(',') {
+        let (key, value) = field.split_once(':')?;
+        let value = value.trim().parse::<u32>().ok()?;
+
+        match key.trim() {
+            "Pkg" => pkg_id = Some(value),
+            "Core" => core_id = Some(value),
+            "SMT" => smt_id = Some(value),
+            "x2APIC" => x2apic_id = Some(value),
+            "Thread" => thread_id = Some(value as usize),
+            _ => return None,
+        }
+    }
+
+    let topo_id = match (pkg_id, core_id, smt_id, x2apic_id) {
+        (Some(pkg_id), Some(core_id), Some(smt_id), Some(x2apic_id)) => Some(TopoId {
+            pkg_id,
+            core_id,
+            smt_id,
+            x2apic_id,
+        }),
+        _ => None,
+    };
+
+    Some((topo_id, thread_id))
+}
+
+/* " 00000007h_x0: eax=00000000h ebx=209C01A9h ecx=00000000h edx=00000000h [FSGSBASE] ..." */
+fn parse_old_line(line: &str) -> Option<RawCpuid> {
+    let hex = |s: &str| -> Option<u32> {
+        u32::from_str_radix(s.strip_suffix('h')?, 16).ok()
+    };
+
+    let mut split = line.split_whitespace();
+    let (leaf, sub_leaf) = split.next()?.strip_suffix(':')?.split_once("_x")?;
+    let leaf = hex(leaf)?;
+    let sub_leaf = u32::from_str_radix(sub_leaf, 16).ok()?;
+
+    let mut reg = |name: &str| -> Option<u32> {
+        hex(split.next()?.strip_prefix(name)?.strip_prefix('=')?)
+    };
+    let [eax, ebx, ecx, edx] = [reg("eax")?, reg("ebx")?, reg("ecx")?, reg("edx")?];
+
+    Some(RawCpuid {
+        leaf,
+        sub_leaf,
+        result: CpuidResult { eax, ebx, ecx, edx },
+    })
+}
+
+/* "00000000_00000000_00000000_00010000  01101000_01110100_01110101_01000001" */
+fn parse_bin_pair(s: &str) -> Option<[u32; 2]> {
+    let bin = |s: &str| -> Option<u32> {
+        if s.len() != 35 { return None }
+
+        u32::from_str_radix(&s.replace('_', ""), 2).ok()
+    };
+
+    let mut split = s.split_whitespace();
+    let pair = [bin(split.next()?)?, bin(split.next()?)?];
+
+    if split.next().is_some() { return None }
+
+    Some(pair)
+}
+
+/* "  0x00000000 0x0:  00000000_00000000_00000000_00010000  01101000_01110100_01110101_01000001" */
+fn parse_bin_head(line: &str) -> Option<(u32, u32, u32, u32)> {
+    let hex = |s: &str| -> Option<u32> {
+        u32::from_str_radix(s.strip_prefix("0x")?, 16).ok()
+    };
+
+    let (input, output) = line.split_once(':')?;
+    let mut input = input.split_whitespace();
+    let leaf = hex(input.next()?)?;
+    let sub_leaf = hex(input.next()?)?;
+    let [eax, ebx] = parse_bin_pair(output)?;
+
+    Some((leaf, sub_leaf, eax, ebx))
+}
+
+/* Ryzen 5 5600G, 12 threads */
+#[cfg(test)]
+pub(crate) fn load_fixture() -> Vec<Snapshot> {
+    parse_raw_dump(include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00.txt"))
+}
+
+#[test]
+fn test_load_raw_dump() {
+    use crate::{args::DumpFormat, disp_snapshots};
+
+    let snapshots = load_fixture();
+
+    assert_eq!(snapshots.len(), 12);
+    assert!(snapshots.iter().enumerate().all(|(i, s)| s.thread_id == Some(i) && s.topo_id.is_some()));
+
+    let bin = include_str!("../dump_result/AMD_Ryzen_5_5600G_with_Radeon_Graphics_00A50F00_bin.txt");
+    let bin = parse_raw_dump(bin);
+
+    assert_eq!(bin.len(), 12);
+    assert!(snapshots.iter().zip(bin.iter()).all(|(a, b)| a.rawcpuid_pool == b.rawcpuid_pool));
+
+    for (dump_fmt, diff) in [
+        (DumpFormat::Raw, true),
+        (DumpFormat::Parse, true),
+        (DumpFormat::Binary, true),
+        (DumpFormat::Raw, false),
+    ] {
+        let loaded = parse_raw_dump(&disp_snapshots(&mut snapshots.clone(), dump_fmt, diff));
+
+        assert_eq!(loaded.len(), 12);
+
+        for (a, b) in snapshots.iter().zip(loaded.iter()) {
+            assert_eq!(a.rawcpuid_pool, b.rawcpuid_pool);
+            assert_eq!((a.thread_id, a.topo_id), (b.thread_id, b.topo_id));
+        }
+    }
+}
+
+#[test]
+fn test_load_old_layout() {
+    use libcpuid_dump::{CpuidSource, CpuVendor};
+
+    let dump = include_str!("../dump_result/AMD_Ryzen_5_2600_00800F82h.txt");
+    let snapshots = parse_raw_dump(dump);
+
+    assert_eq!(snapshots.len(), 12);
+    assert!(snapshots.iter().enumerate().all(|(i, s)| s.thread_id == Some(i)));
+    assert_eq!(snapshots[0].cpu_vendor, CpuVendor::AuthenticAMD);
+    assert_eq!(snapshots[0].cpuid(0x1, 0x0).eax, 0x00800F82);
+    /* Initial APIC ID of "Core ID: 1 / Thread: 1" */
+    assert_eq!(snapshots[1].cpuid(0x1, 0x0).ebx >> 24, 2);
+}
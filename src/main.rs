@@ -3,8 +3,7 @@
 
 use core::arch::x86_64::CpuidResult;
 
-use libcpuid_dump::TopoId;
-use libcpuid_dump::{cpuid, CpuVendor};
+use libcpuid_dump::{cpuid, CpuVendor, HypervisorInfo, RawCpuid, SgxInfo, Snapshot, TopoId};
 
 pub const INPUT_WIDTH: usize = "  0x00000000 0x0:  ".len();
 pub const OUTPUT_WIDTH: usize = "0x00000000 ".len() * 4;
@@ -22,148 +21,53 @@ mod args;
 use args::*;
 
 mod load_aida64_log;
-
-/// Main flow:
-///    pub struct RawCpuid {
-///        pub leaf: u32,
-///        pub sub_leaf: u32,
-///        // https://doc.rust-lang.org/core/arch/x86_64/struct.CpuidResult.html
-///        pub result: CpuidResult {
-///            pub eax: u32,
-///            pub ebx: u32,
-///            pub ecx: u32,
-///            pub edx: u32,
-///        },
-///    } 
-///    // src/main.rs
-///    MainOpt::parse() -> MainOpt
-///            |
-///    opt.rawcpuid_pool(&leaf_pool()) -> Vec<RawCpuid>
-///            |
-///    // src/raw_cpuid.rs
-///    let parsed_pool: Vec<u8>;
-///    cpuid_parse: {
-///        for raw_cpuid in cpuid_pool {
-///            // src/raw_cpuid.rs, src/parse/*
-///            let cpuid_parsed: String = raw_cpuid.parse();
-///            parsed_pool.extend(cpuid_parsed.into_bytes());
-///        }
-///    }
-///            |
-///    // src/main.rs
-///    dump_write(&parsed_pool) // print, write stdout
-///    
-
-fn leaf_pool() -> Vec<(u32, u32)> {
-    let mut leaf_pool: Vec<(u32, u32)> = Vec::with_capacity(64);
-
-    /* LFuncStd: largest standard function */
-    let max_std_leaf = RawCpuid::exe(0x0, 0x0).result.eax;
-    /* LFuncExt: largest extended function */
-    let max_ext_leaf = RawCpuid::exe(0x8000_0000, 0x0).result.eax;
-
-    /* Base */
-    for leaf in 0x0..=max_std_leaf {
-        match leaf {
-            /* Cache Properties, Intel */
-            0x4 => for sub_leaf in 0x0..=0x4 {
-                leaf_pool.push((leaf, sub_leaf))
-            },
-            0x7 => {
-                /* CPUID[Leaf=0x7, SubLeaf=0x0].EAX, StructExtFeatIdMax */
-                let max_sub_leaf = RawCpuid::exe(0x7, 0x0).result.eax;
-
-                for sub_leaf in 0x0..=max_sub_leaf {
-                    leaf_pool.push((leaf, sub_leaf))
-                }
-            },
-            /*  Extended Topology Enumeration, Intel, AMD Zen 2 <=
-                SMT_LEVEL = 0,
-                CORE_LEVEL = 1,
-            */
-            0xB => for sub_leaf in 0x0..=0x1 {
-                leaf_pool.push((leaf, sub_leaf))
-            },
-            /* 0xD: Processor Extended State Enumeration */
-            0xD => for sub_leaf in 0x0..0xF {
-                leaf_pool.push((leaf, sub_leaf))
-            },
-            /* 0x18: Deterministic Address Translation Parameters, Intel */
-            0x18 => {
-                let max_sub_leaf = RawCpuid::exe(0x18, 0x0).result.eax;
-
-                for sub_leaf in 0x0..max_sub_leaf {
-                    leaf_pool.push((leaf, sub_leaf))
-                }
-            },
-            /* 0x1F: V2 Extended Topology Enumeration Leaf, Intel */
-            0x1F => for sub_leaf in 0x0..=0x4 {
-                leaf_pool.push((0x1F, sub_leaf))
-            },
-            _ => leaf_pool.push((leaf, 0x0)),
-        }
-    }
-
-    /* Ext */
-    for leaf in 0x8000_0000..=max_ext_leaf {
-        match leaf {
-            /* Cache Properties, AMD, same format as Intel Leaf 0x4 */
-            0x8000_001D => for sub_leaf in 0x0..=0x4 {
-                leaf_pool.push((leaf, sub_leaf))
-            },
-            /* AMD Platform QoS Enforcement for Memory Bandwidth */
-            0x8000_0020 => for sub_leaf in 0x0..=0x1 {
-                leaf_pool.push((leaf, sub_leaf))
-            },
-            /* AMD Extended CPU Topology */
-            0x8000_0026 => for sub_leaf in 0x0..=0x4 {
-                leaf_pool.push((leaf, sub_leaf))
-            },
-            _ => leaf_pool.push((leaf, 0x0)),
-        }
-    }
-
-    leaf_pool
-}
-
-#[derive(Debug, Clone)]
-struct CpuidDump {
-    pub cpu_vendor: CpuVendor,
-    pub rawcpuid_pool: Vec<RawCpuid>,
-    pub topo_id: Option<TopoId>,
-    pub thread_id: Option<usize>,
+mod json_dump;
+mod load_cpuid_r;
+mod load_raw_dump;
+mod load_instlat;
+mod load_dump;
+mod dump_diff;
+
+// Main flow:
+//    pub struct RawCpuid {
+//        pub leaf: u32,
+//        pub sub_leaf: u32,
+//        // https://doc.rust-lang.org/core/arch/x86_64/struct.CpuidResult.html
+//        pub result: CpuidResult {
+//            pub eax: u32,
+//            pub ebx: u32,
+//            pub ecx: u32,
+//            pub edx: u32,
+//        },
+//    } 
+//    // src/main.rs
+//    MainOpt::parse() -> MainOpt
+//            |
+//    // lib/snapshot.rs
+//    Snapshot::new(&leaf_pool(), ..) -> Snapshot { rawcpuid_pool: Vec<RawCpuid>, .. }
+//            |
+//    // src/raw_cpuid.rs
+//    let parsed_pool: Vec<u8>;
+//    cpuid_parse: {
+//        for raw_cpuid in cpuid_pool {
+//            // src/raw_cpuid.rs, src/parse/*
+//            let cpuid_parsed: String = raw_cpuid.parse();
+//            parsed_pool.extend(cpuid_parsed.into_bytes());
+//        }
+//    }
+//            |
+//    // src/main.rs
+//    dump_write(&parsed_pool) // print, write stdout
+//    
+
+trait SnapshotDisp {
+    fn top_disp(&self, dump_fmt: DumpFormat) -> String;
+    fn disp(&self, dump_fmt: DumpFormat) -> String;
+    fn select_pool(&self, dump_fmt: DumpFormat) -> String;
+    fn topo_info_head(&self) -> String;
 }
 
-impl CpuidDump {
-    fn new(leaf_pool: &[(u32, u32)], skip_zero: bool) -> Self {
-        let cpu_vendor = CpuVendor::get();
-
-        let rawcpuid_pool = leaf_pool.iter().filter_map(|(leaf, sub_leaf)| {
-            let rawcpuid = RawCpuid::exe(*leaf, *sub_leaf);
-            
-            if skip_zero && rawcpuid.check_result_zero() {
-                None
-            } else {
-                Some(rawcpuid)
-            }
-        }).collect();
-        let topo_id = TopoId::get_topo_info();
-
-        Self {
-            cpu_vendor,
-            rawcpuid_pool,
-            topo_id,
-            thread_id: None,
-        }
-    }
-
-    fn new_with_thread_id(leaf_pool: &[(u32, u32)], skip_zero: bool, thread_id: usize) -> Self {
-        let mut tmp = Self::new(leaf_pool, skip_zero);
-        tmp.thread_id = Some(thread_id);
-
-        tmp
-    }
-
+impl SnapshotDisp for Snapshot {
     fn top_disp(&self, dump_fmt: DumpFormat) -> String {
         [
             self.topo_info_head(),
@@ -181,11 +85,34 @@ impl CpuidDump {
     }
 
     fn select_pool(&self, dump_fmt: DumpFormat) -> String {
-        let fmt_func = dump_fmt.rawcpuid_fmt_func();
+        let Some(fmt_func) = dump_fmt.rawcpuid_fmt_func() else {
+            return json_dump::snapshots_to_json(std::slice::from_ref(self));
+        };
+        /* The hypervisor leaves are decoded by the hypervisor vendor, not by the CPU vendor */
+        let hv_list = match dump_fmt {
+            DumpFormat::Parse => HypervisorInfo::list_from_source(&self.rawcpuid_pool.as_slice()),
+            _ => Vec::new(),
+        };
+        /* The leaf 0x12 is decoded only if SGX (`CPUID.(EAX=07h, ECX=0):EBX[2]`) is set */
+        let sgx = match dump_fmt {
+            DumpFormat::Parse => SgxInfo::get_from_source(&self.rawcpuid_pool.as_slice()),
+            _ => None,
+        };
 
         self.rawcpuid_pool
             .iter()
-            .map(|rawcpuid| fmt_func(rawcpuid, &self.cpu_vendor))
+            .map(|rawcpuid| {
+                let leaf = rawcpuid.leaf;
+                let hv = hv_list.iter().find(|hv| hv.base <= leaf && leaf <= hv.max_leaf);
+
+                match (hv, &sgx) {
+                    (Some(hv), _) => rawcpuid.result(&rawcpuid.result.hypervisor_40h(hv, leaf)),
+                    (None, Some(sgx)) if leaf == 0x12 => {
+                        rawcpuid.result(&rawcpuid.result.sgx_intel_00_12h(sgx, rawcpuid.sub_leaf))
+                    },
+                    _ => fmt_func(rawcpuid, &self.cpu_vendor),
+                }
+            })
             .collect()
     }
 
@@ -218,64 +145,47 @@ impl CpuidDump {
     }
 }
 
+/// Display the snapshots, the first one with the header.
+/// If `diff` is true, omit the results that are the same as the first one.
+fn disp_snapshots(snapshots: &mut [Snapshot], dump_fmt: DumpFormat, diff: bool) -> String {
+    if let DumpFormat::Json = dump_fmt {
+        return json_dump::snapshots_to_json(snapshots);
+    }
+
+    let Some((first, rest)) = snapshots.split_first_mut() else { return String::new() };
+
+    let s = first.top_disp(dump_fmt);
+    let ss: String = rest.iter_mut().map(|snapshot| {
+        if diff {
+            snapshot.retain_diff(first);
+        }
+
+        snapshot.disp(dump_fmt)
+    }).collect();
+
+    format!("{s}{ss}")
+}
+
 fn dump_all_threads(
     leaf_pool: &[(u32, u32)],
     skip_zero: bool,
     dump_fmt: DumpFormat,
     diff: bool,
+    cpuid_dev: bool,
 ) -> String {
-    use std::thread;
-    use std::sync::Arc;
-    use libcpuid_dump::util;
-
-    let leaf_pool = Arc::from(leaf_pool);
-    let cpu_list = util::cpu_set_list().unwrap();
-    let mut handles: Vec<thread::JoinHandle<_>> = Vec::with_capacity(cpu_list.len());
-
-    let first = {
-        /* To confine the effects of pin_thread */
-        thread::scope(|s| s.spawn(|| {
-            let cpu = cpu_list[0];
-            util::pin_thread(cpu).unwrap();
-
-            Arc::new(CpuidDump::new_with_thread_id(&leaf_pool, skip_zero, cpu))
-        }).join().unwrap())
-    };
-
-    for cpu in &cpu_list[1..] {
-        let cpu = *cpu;
-        let leaf_pool = Arc::clone(&leaf_pool);
-        let first = Arc::clone(&first);
-
-        handles.push(thread::spawn(move || {
-            util::pin_thread(cpu).unwrap();
-
-            let cpuid_dump = {
-                let mut sub = CpuidDump::new_with_thread_id(&leaf_pool, skip_zero, cpu);
-
-                if diff {
-                    let mut first_rawcpuid_pool = first.rawcpuid_pool.iter();
-
-                    sub.rawcpuid_pool.retain(|sub| {
-                        let Some(first) = first_rawcpuid_pool.next() else { return false };
-                        first != sub
-                    });
-                }
-
-                sub
-            };
-
-            cpuid_dump
-        }));
+    #[cfg(target_os = "linux")]
+    if cpuid_dev {
+        match Snapshot::all_threads_from_cpuid_dev(leaf_pool, skip_zero) {
+            Ok(mut snapshots) => return disp_snapshots(&mut snapshots, dump_fmt, diff),
+            Err(err) => eprintln!("cpuid device is not available ({err}), fall back to pinning the thread"),
+        }
     }
+    #[cfg(not(target_os = "linux"))]
+    let _ = cpuid_dev;
 
-    let s = first.top_disp(dump_fmt);
-    let ss: String = handles.into_iter().filter_map(|h| {
-        let cpuid_dump = h.join().ok()?;
-        Some(cpuid_dump.disp(dump_fmt))
-    }).collect();
+    let mut snapshots = Snapshot::all_threads(leaf_pool, skip_zero).unwrap();
 
-    format!("{s}{ss}")
+    disp_snapshots(&mut snapshots, dump_fmt, diff)
 }
 
 fn main() {
@@ -288,9 +198,24 @@ fn main() {
         MainOpt { save_path: Some(ref path), .. } => {
             opt.save_file(path).expect("faild save_file")
         },
+        MainOpt { diff_path: Some(ref path), .. } => {
+            opt.diff_files(path).expect("faild diff_files")
+        },
+        MainOpt { load: Some(ref path), .. } => {
+            opt.load(path).expect("faild load")
+        },
         MainOpt { load_aida64: Some(ref path), .. } => {
             opt.load_aida64(path).expect("faild load_aida64")
         },
+        MainOpt { load_json: Some(ref path), .. } => {
+            opt.load_json(path).expect("faild load_json")
+        },
+        MainOpt { load_raw: Some(ref path), .. } => {
+            opt.load_raw(path).expect("faild load_raw")
+        },
+        MainOpt { load_cpuid_r: Some(ref path), .. } => {
+            opt.load_cpuid_r(path).expect("faild load_cpuid_r")
+        },
         _ => {
             dump_write(&opt.dump_pool()).expect("faild dump_write")
         },
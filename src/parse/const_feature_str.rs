@@ -253,8 +253,13 @@ pub(crate) const fn ftr_00_07_edx_x1() -> [&'static str; 32] {
 
     ftr[4] = "AVX-VNNI-INT8";
     ftr[5] = "AVX-NE-CONVERT";
+    ftr[8] = "AMX-COMPLEX";
+    ftr[10] = "AVX-VNNI-INT16";
     ftr[14] = "PREFETCHITI";
+    ftr[15] = "USER_MSR"; // URDMSR, UWRMSR
     ftr[18] = "CET_SSS"; // CET Supervisor Shadow Stack
+    ftr[19] = "AVX10"; // Converged Vector ISA, see Leaf 0x24
+    ftr[21] = "APX_F"; // Advanced Performance Extensions, Foundation
 
     ftr
 }
@@ -272,33 +277,6 @@ pub(crate) const fn ftr_00_07_edx_x2() -> [&'static str; 32] {
     ftr
 }
 
-/* Ref: https://github.com/torvalds/linux/blob/master/arch/x86/kernel/fpu/xstate.c */
-pub(crate) const fn xfeature_mask_00_0d_eax_x0() -> [&'static str; 32] {
-    let mut ftr = [""; 32];
-
-    ftr[0] = "X87";
-    ftr[1] = "SSE";
-    ftr[2] = "AVX256";
-    ftr[3] = "MPX bounds";
-    ftr[4] = "MPX CSR";
-    ftr[5] = "AVX512 opmask"; // KREGS
-    ftr[6] = "AVX512 Hi256";
-    ftr[7] = "AVX512 ZMM_Hi256";
-    ftr[8] = "Processor Trace"; // unused
-    ftr[9] = "Protection Key User";
-    ftr[10] = "PASID";
-    ftr[11] = "";
-    ftr[12] = "";
-    ftr[13] = "";
-    ftr[14] = "";
-    ftr[15] = "";
-    ftr[16] = "";
-    ftr[17] = "AMX Tile config";
-    ftr[18] = "AMX Tile data";
-
-    ftr
-}
-
 pub(crate) const fn xsave_00_0d_eax_x1() -> [&'static str; 32] {
     let mut ftr = [""; 32];
 
@@ -311,11 +289,59 @@ pub(crate) const fn xsave_00_0d_eax_x1() -> [&'static str; 32] {
     ftr
 }
 
-pub(crate) const fn xsave_00_0d_ecx_x1() -> [&'static str; 32] {
+pub(crate) const fn ftr_intel_00_19_eax_x0() -> [&'static str; 32] {
+    let mut ftr = [""; 32];
+
+    ftr[0] = "CPL0-only";
+    ftr[1] = "NoEncrypt";
+    ftr[2] = "NoDecrypt";
+
+    ftr
+}
+
+pub(crate) const fn ftr_intel_00_19_ebx_x0() -> [&'static str; 32] {
+    let mut ftr = [""; 32];
+
+    ftr[0] = "AESKLE"; // AES Key Locker instructions are enabled
+    ftr[2] = "AES_Wide_KL";
+    ftr[4] = "IWKeyBackup";
+
+    ftr
+}
+
+pub(crate) const fn ftr_intel_00_19_ecx_x0() -> [&'static str; 32] {
     let mut ftr = [""; 32];
 
-    ftr[11] = "CET User";
-    ftr[12] = "CET SuperVisor";
+    ftr[0] = "NoBackup";
+    ftr[1] = "RandomIWKey"; // KeySource encoding of 1
+
+    ftr
+}
+
+pub(crate) const fn ftr_intel_00_14_ebx_x0() -> [&'static str; 32] {
+    let mut ftr = [""; 32];
+
+    ftr[0] = "CR3Filter";
+    ftr[1] = "PSB/CYC"; // Configurable PSB and Cycle-Accurate Mode
+    ftr[2] = "IPFilter"; // IP Filtering, TraceStop filtering
+    ftr[3] = "MTC";
+    ftr[4] = "PTWRITE";
+    ftr[5] = "PwrEvtTrace";
+    ftr[6] = "PSB/PMI_Preserve";
+    ftr[7] = "EventTrace";
+    ftr[8] = "TNT_Disable";
+
+    ftr
+}
+
+pub(crate) const fn ftr_intel_00_14_ecx_x0() -> [&'static str; 32] {
+    let mut ftr = [""; 32];
+
+    ftr[0] = "ToPA"; // Table of Physical Addresses
+    ftr[1] = "ToPA_MultiEntry";
+    ftr[2] = "SingleRange";
+    ftr[3] = "TraceTransport";
+    ftr[31] = "LIP"; // IP payloads have LIP values
 
     ftr
 }
@@ -523,15 +549,18 @@ pub const fn ftr_amd_80_1f_eax_x0() -> [&'static str; 32] {
     ftr
 }
 
-/*
 pub(crate) const fn ftr_amd_80_20_ebx_x0() -> [&'static str; 32] {
     let mut ftr = [""; 32];
 
-    ftr[1] = "MBE"; // Memory bandwidth enforcement
+    ftr[1] = "L3MBE"; // L3 Memory bandwidth enforcement
+    ftr[2] = "L3SMBE"; // L3 Slow Memory bandwidth enforcement
+    ftr[3] = "BMEC"; // Bandwidth Monitoring Event Configuration
+    ftr[4] = "L3RR"; // L3 Range Reservation
+    ftr[5] = "ABMC"; // Assignable Bandwidth Monitoring Counters
+    ftr[6] = "SDCIAE"; // Smart Data Cache Injection Allocation Enforcement
 
-    return ftr;
+    ftr
 }
-*/
 
 pub(crate) const fn ftr_amd_80_21_eax_x0() -> [&'static str; 32] {
     let mut ftr = [""; 32];
@@ -541,7 +570,7 @@ pub(crate) const fn ftr_amd_80_21_eax_x0() -> [&'static str; 32] {
     ftr[2] = "LFenceAlwaysSerializing";
     ftr[3] = "SmmPgCfgLock";
     // ftr[4] = "";
-    // ftr[5] = "";
+    ftr[5] = "VERW_CLEAR"; // The memory form of VERW clears the CPU buffers
     ftr[6] = "NullSelectClearsBase";
     ftr[7] = "UpperAddressIgnore";
     ftr[8] = "AutomaticIBRS";
@@ -556,10 +585,103 @@ pub(crate) const fn ftr_amd_80_21_eax_x0() -> [&'static str; 32] {
     ftr[17] = "CpuidUserDis"; // GpOnUserCpuid
     ftr[18] = "EPSF"; // Enhanced Predictive Store Forwarding
     ftr[19] = "FAST_REP_SCASB"; // Enhanced REP MOVSB/STOSB
+    ftr[20] = "PREFETCHI"; // Prefetch Instruction
     ftr[21] = "FP512_DOWNGRADE";
     ftr[22] = "Workload_Class"; // WL_CLASS_SUPPORT
     ftr[24] = "ERAPS"; // enhanced return address predictor security
     ftr[27] = "SBPB"; // Selective Branch Predictor Barrier
+    ftr[28] = "IBPB_BRTYPE"; // IBPB flushes all branch type predictions
+    ftr[29] = "SRSO_NO"; // Not vulnerable to SRSO
+    ftr[30] = "SRSO_USER_KERNEL_NO"; // Not vulnerable to SRSO at the user-kernel boundary
+    ftr[31] = "SRSO_BP_SPEC_REDUCE";
+
+    ftr
+}
+
+/* Ref: https://github.com/torvalds/linux/blob/master/arch/x86/include/asm/cpufeatures.h */
+pub(crate) const fn ftr_centaur_c0_01_edx_x0() -> [&'static str; 32] {
+    let mut ftr = [""; 32];
+
+    ftr[2] = "RNG"; // PadLock Random Number Generator (XSTORE)
+    ftr[3] = "RNG_EN";
+    ftr[6] = "ACE"; // PadLock Advanced Cryptography Engine (XCRYPT)
+    ftr[7] = "ACE_EN";
+    ftr[8] = "ACE2";
+    ftr[9] = "ACE2_EN";
+    ftr[10] = "PHE"; // PadLock Hash Engine
+    ftr[11] = "PHE_EN";
+    ftr[12] = "PMM"; // PadLock Montgomery Multiplier
+    ftr[13] = "PMM_EN";
+
+    ftr
+}
+
+/* Ref: https://github.com/torvalds/linux/blob/master/include/hyperv/hvgdk_mini.h */
+pub(crate) const fn ftr_hyperv_40_03_eax() -> [&'static str; 32] {
+    let mut ftr = [""; 32];
+
+    ftr[0] = "VpRuntime";
+    ftr[1] = "PartitionRefCounter"; // Partition Reference Counter
+    ftr[2] = "SynIC"; // Synthetic Interrupt Controller
+    ftr[3] = "SynTimer"; // Synthetic Timer
+    ftr[4] = "IntrCtrl"; // APIC EOI, ICR, TPR
+    ftr[5] = "HypercallMsr";
+    ftr[6] = "VpIndex";
+    ftr[7] = "ResetMsr";
+    ftr[8] = "StatsMsr";
+    ftr[9] = "PartitionRefTsc"; // Partition Reference TSC
+    ftr[10] = "GuestIdleMsr";
+    ftr[11] = "FrequencyMsr";
+    ftr[12] = "DebugMsr";
+    ftr[13] = "Reenlightenment";
+
+    ftr
+}
+
+pub(crate) const fn ftr_hyperv_40_03_ebx() -> [&'static str; 32] {
+    let mut ftr = [""; 32];
+
+    ftr[0] = "CreatePartitions";
+    ftr[1] = "AccessPartitionId";
+    ftr[2] = "AccessMemoryPool";
+    ftr[4] = "PostMessages";
+    ftr[5] = "SignalEvents";
+    ftr[6] = "CreatePort";
+    ftr[7] = "ConnectPort";
+    ftr[8] = "AccessStats";
+    ftr[11] = "Debugging";
+    ftr[12] = "CpuManagement";
+    ftr[16] = "AccessVSM"; // Virtual Secure Mode
+    ftr[17] = "AccessVpRegisters";
+    ftr[20] = "ExtHypercalls";
+    ftr[21] = "StartVp";
+    ftr[22] = "Isolation";
+
+    ftr
+}
+
+pub(crate) const fn ftr_hyperv_40_03_edx() -> [&'static str; 32] {
+    let mut ftr = [""; 32];
+
+    ftr[0] = "MWAIT"; // deprecated
+    ftr[1] = "GuestDebugging";
+    ftr[2] = "PerfMonitor";
+    ftr[3] = "PcpuDynPartitioning";
+    ftr[4] = "XmmHypercallInput";
+    ftr[5] = "GuestIdle";
+    ftr[6] = "HvSleepState";
+    ftr[7] = "NumaDistanceQuery";
+    ftr[8] = "TimerFrequency";
+    ftr[9] = "SynMCInjection"; // Synthetic Machine Check
+    ftr[10] = "GuestCrashMsr";
+    ftr[11] = "DebugMsr";
+    ftr[12] = "NPIEP";
+    ftr[13] = "DisableHv";
+    ftr[14] = "ExtGvaRangesFlush";
+    ftr[15] = "XmmHypercallOutput";
+    ftr[17] = "SintPollingMode";
+    ftr[18] = "HypercallMsrLock";
+    ftr[19] = "DirectSynTimer";
 
     ftr
 }
@@ -15,3 +15,9 @@ pub use parse_amd::*;
 
 mod parse_intel;
 pub use parse_intel::*;
+
+mod parse_zhaoxin;
+pub use parse_zhaoxin::*;
+
+mod parse_hypervisor;
+pub use parse_hypervisor::*;
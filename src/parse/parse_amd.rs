@@ -30,8 +30,10 @@ pub trait ParseAMD {
     fn ibs_amd_80_1bh(&self) -> String;
     fn cpu_topo_amd_80_1eh(&self) -> String;
     fn encrypt_ftr_amd_80_1fh(&self) -> String;
-    fn reduction_phys_addr_amd_80_1fh(&self) -> String;
+    fn mem_encrypt_amd_80_1fh(&self) -> String;
+    fn pqe_amd_80_20h(&self, sub_leaf: u32) -> String;
     fn ext_amd_80_21h(&self) -> String;
+    fn perfmon_amd_80_22h(&self) -> String;
     fn amd_ext_topo_80_26h(&self) -> String;
 }
 
@@ -174,34 +176,99 @@ impl ParseAMD for CpuidResult {
         align_mold_ftr(&str_detect_ftr(self.eax, &ftr_amd_80_1f_eax_x0()))
     }
 
-    fn reduction_phys_addr_amd_80_1fh(&self) -> String {
-        // Reduction of physical address space in bits when 
-        // memory encryption is enabled (0 indicates no reduction).
-        // [Reserved]: Bit16-31
-        // VmplSupported: Bit12-15
-        // MemEncryptPhysAddWidth: Bit6-11
-        // CBit: Bit00-05
-        let reduction_size = (self.ebx >> 6) & 0x3F;
+    fn mem_encrypt_amd_80_1fh(&self) -> String {
+        let mem_encrypt = libcpuid_dump::AmdMemEncrypt::from(self);
 
-        if 0 < reduction_size {
-            format!("{LN_PAD}[MemEncryptPhysAddWidth: {reduction_size}-bits]")
-        } else {
-            "".to_string()
+        let mut v = Vec::with_capacity(4);
+
+        if mem_encrypt.sme() || mem_encrypt.sev() {
+            v.push(format!("{LN_PAD}[C-bit: {}]", mem_encrypt.c_bit));
+        }
+        if 0 < mem_encrypt.phys_addr_reduction {
+            v.push(format!("{LN_PAD}[MemEncryptPhysAddWidth: {}-bits]", mem_encrypt.phys_addr_reduction));
+        }
+        if mem_encrypt.vmpl() {
+            v.push(format!("{LN_PAD}[VMPL: {}]", mem_encrypt.num_vmpl));
+        }
+        if mem_encrypt.sev() {
+            v.push(format!(
+                "{LN_PAD}[Encrypted Guests: {}, Min SEV ASID: {}]",
+                mem_encrypt.num_encrypted_guests,
+                mem_encrypt.min_sev_no_es_asid,
+            ));
+        }
+
+        v.concat()
+    }
+
+    fn pqe_amd_80_20h(&self, sub_leaf: u32) -> String {
+        use libcpuid_dump::{AmdBmec, AmdBwEnforce};
+
+        match sub_leaf {
+            0x0 => align_mold_ftr(&str_detect_ftr(self.ebx, &ftr_amd_80_20_ebx_x0())),
+            0x1 | 0x2 => {
+                let mbe = AmdBwEnforce::from(self);
+
+                format!(
+                    "[{} BW_LEN: {}, COS: {}]",
+                    if sub_leaf == 0x1 { "L3MBE" } else { "L3SMBE" },
+                    mbe.bw_len,
+                    mbe.max_cos + 1,
+                )
+            },
+            0x3 => {
+                let bmec = AmdBmec::from(self);
+
+                format!("[BMEC Events: {}, Valid: {:#X}]", bmec.num_events, bmec.valid_events)
+            },
+            _ => "".to_string(),
         }
     }
 
     fn ext_amd_80_21h(&self) -> String {
-        let ftr = align_mold_ftr(&str_detect_ftr(self.eax, &ftr_amd_80_21_eax_x0()));
-        let ucode_patch_size = self.ebx & 0xFFF;
-
-        if 0 < ucode_patch_size {
-            format!("\
-                {ftr}\
-                {LN_PAD}[uCodePatchSize: {ucode_patch_size} Bytes]\
-            ")
-        } else {
-            ftr
+        let ext_ftr = libcpuid_dump::AmdExtFeature2::from(self);
+        let mut v = vec![align_mold_ftr(&str_detect_ftr(self.eax, &ftr_amd_80_21_eax_x0()))];
+
+        if 0 < ext_ftr.microcode_patch_size {
+            v.push(format!("{LN_PAD}[uCodePatchSize: {} Bytes]", ext_ftr.microcode_patch_bytes()));
+        }
+        if let Some(calls) = ext_ftr.rap_flush_calls() {
+            v.push(format!("{LN_PAD}[RAP Size: {}, Flush: {calls} CALLs]", ext_ftr.rap_size));
         }
+
+        v.concat()
+    }
+
+    fn perfmon_amd_80_22h(&self) -> String {
+        use libcpuid_dump::AmdPerfMon;
+
+        let perfmon = AmdPerfMon::from(self);
+        let mut ftr: Vec<String> = Vec::with_capacity(3);
+        {
+            if perfmon.perfmon_v2 { ftr.push("PerfMonV2".to_string()) }
+            if perfmon.lbr_stack { ftr.push("LbrStack".to_string()) }
+            if perfmon.lbr_and_pmc_freeze { ftr.push("LbrAndPmcFreeze".to_string()) }
+        }
+
+        let lbr = if perfmon.lbr_stack {
+            format!("{LN_PAD}[LBR Stack: {}]", perfmon.lbr_stack_size)
+        } else {
+            "".to_string()
+        };
+
+        format!("\
+            {ftr}\
+            {LN_PAD}[Core PMC: {core}]\
+            {lbr}\
+            {LN_PAD}[DF PMC: {df}, UMC PMC: {umc}]\
+            {LN_PAD}[Active UMC: {active_umc:#X}]\
+        ",
+            ftr = align_mold_ftr(&ftr),
+            core = perfmon.num_core_pmc,
+            df = perfmon.num_df_pmc,
+            umc = perfmon.num_umc_pmc,
+            active_umc = perfmon.active_umc_mask,
+        )
     }
 
     fn amd_ext_topo_80_26h(&self) -> String {
@@ -11,6 +11,8 @@ pub trait ParseGeneric {
     fn feature_00_07h_x2(&self) -> String;
     fn topo_ext_00_0bh(&self) -> String;
     fn xstate_00_0dh(&self, sub_leaf: u32) -> String;
+    fn rdt_monitor_00_0fh(&self, sub_leaf: u32) -> String;
+    fn rdt_alloc_00_10h(&self, sub_leaf: u32) -> String;
     fn feature_80_01h(&self) -> String;
     fn addr_size_80_08h(&self) -> String;
     fn ftr_ext_id_80_08h_ebx(&self) -> String;
@@ -131,41 +133,140 @@ impl ParseGeneric for CpuidResult {
     }
 
     fn xstate_00_0dh(&self, sub_leaf: u32) -> String {
-        let size = |eax: u32, txt: &str| -> String {
-            /* 00_0D_X{SUB}:EAX is the state size, EAX = 0 indicates not supported it */
-            if eax != 0x0 {
-                format!("[{txt:<16} save size: {eax:>4}B]")
-            } else {
-                "".to_string()
-            }
-        };
+        use libcpuid_dump::{XStateComponent, XStateKind};
 
-        let eax = self.eax;
+        let names = |mask: u32| -> Vec<String> {
+            (0..32u8)
+                .filter(|i| (mask >> i) & 0b1 != 0)
+                .map(|i| XStateComponent::name(i).to_string())
+                .collect()
+        };
 
         match sub_leaf {
             0x0 => {
                 [
-                    format!("[-XFEATURE Mask-]{LN_PAD}"),
-                    align_mold_ftr(&str_detect_ftr(eax, &xfeature_mask_00_0d_eax_x0())),
+                    format!("[-XCR0 Mask-]{LN_PAD}"),
+                    align_mold_ftr(&names(self.eax)),
+                    format!("{LN_PAD}[Size: {:>5}B, Max: {:>5}B]", self.ebx, self.ecx),
                 ]
                 .concat()
             },
             0x1 => {
-                align_mold_ftr(&[
-                    str_detect_ftr(self.eax, &xsave_00_0d_eax_x1()),
-                    str_detect_ftr(self.ecx, &xsave_00_0d_ecx_x1()),
-                ].concat())
+                let xss = if self.ecx != 0 {
+                    format!("{LN_PAD}[-IA32_XSS Mask-]{LN_PAD}{}", align_mold_ftr(&names(self.ecx)))
+                } else {
+                    "".to_string()
+                };
+
+                [
+                    align_mold_ftr(&str_detect_ftr(self.eax, &xsave_00_0d_eax_x1())),
+                    xss,
+                    format!("{LN_PAD}[Compacted Size: {:>5}B]", self.ebx),
+                ]
+                .concat()
+            },
+            _ => {
+                /* 00_0D_X{SUB}:EAX is the state size, EAX = 0 indicates not supported it */
+                if self.eax == 0x0 {
+                    return "".to_string();
+                }
+
+                let c = XStateComponent::from_cpuid(sub_leaf as u8, self);
+                let mut attr = Vec::with_capacity(4);
+                {
+                    match c.kind {
+                        XStateKind::User => attr.push(format!("Offset: {}", c.offset)),
+                        XStateKind::Supervisor => attr.push(c.kind.to_string()),
+                    }
+                    if c.align_64 { attr.push("Align64".to_string()) }
+                    if c.xfd { attr.push("XFD".to_string()) }
+                }
+
+                format!(
+                    "[{:<17} size: {:>4}B]{LN_PAD}{}",
+                    XStateComponent::name(c.index),
+                    c.size,
+                    align_mold_ftr(&attr),
+                )
+            },
+        }
+    }
+
+    fn rdt_monitor_00_0fh(&self, sub_leaf: u32) -> String {
+        match sub_leaf {
+            0x0 => {
+                let l3 = if ((self.edx >> 1) & 0b1) != 0 {
+                    format!("{LN_PAD}[L3]")
+                } else {
+                    "".to_string()
+                };
+
+                format!("[Max RMID: {}]{l3}", self.ebx)
+            },
+            0x1 => {
+                let mon = libcpuid_dump::RdtL3Monitoring::from(self);
+                let mut ftr = Vec::with_capacity(3);
+                {
+                    if mon.occupancy { ftr.push("Occupancy".to_string()) }
+                    if mon.total_mbm { ftr.push("TotalMBM".to_string()) }
+                    if mon.local_mbm { ftr.push("LocalMBM".to_string()) }
+                }
+
+                format!("\
+                    [L3 Max RMID: {}]\
+                    {LN_PAD}[UpscalingFactor: {}, CounterWidth: {}-bit]\
+                    {LN_PAD}{}\
+                ",
+                    mon.max_rmid,
+                    mon.upscaling_factor,
+                    mon.counter_width,
+                    align_mold_ftr(&ftr),
+                )
             },
-            0x2 => size(eax, "YMMHI"),
-            0x3 | 0x4 => size(eax, "MPX"),
-            0x5 => size(eax, "KREGS"),
-            0x6 => size(eax, "ZMMHI"),
-            0x7 => size(eax, "HIZMM"),
-            0x8 => size(eax, "IA32_XSS"),
-            0x9 => size(eax, "Protection Key"),
-            0xB => size(eax, "CET User"),
-            0xC => size(eax, "CET SuperVisor"),
-            _ => size(eax, "Unknown"),
+            _ => "".to_string(),
+        }
+    }
+
+    fn rdt_alloc_00_10h(&self, sub_leaf: u32) -> String {
+        use libcpuid_dump::{RdtCat, RdtMba};
+
+        match sub_leaf {
+            0x0 => {
+                let res = str_detect_ftr(self.ebx, &["", "L3 CAT", "L2 CAT", "MBA"]);
+
+                align_mold_ftr(&res)
+            },
+            0x1 | 0x2 => {
+                let cat = RdtCat::from(self);
+                let mut ftr = Vec::with_capacity(2);
+                {
+                    if cat.cdp { ftr.push("CDP".to_string()) }
+                    if cat.non_contiguous_cbm { ftr.push("NonContiguousCBM".to_string()) }
+                }
+                let ftr = if ftr.is_empty() {
+                    "".to_string()
+                } else {
+                    format!("{LN_PAD}{}", align_mold_ftr(&ftr))
+                };
+
+                format!(
+                    "[L{} CAT, CBM: {}-bit, COS: {}]{ftr}",
+                    if sub_leaf == 0x1 { 3 } else { 2 },
+                    cat.cbm_len,
+                    cat.max_cos as u32 + 1,
+                )
+            },
+            0x3 => {
+                let mba = RdtMba::from(self);
+                let linear = if mba.linear { format!("{LN_PAD}[Linear]") } else { "".to_string() };
+
+                format!(
+                    "[MBA Max Throttling: {}, COS: {}]{linear}",
+                    mba.max_throttling,
+                    mba.max_cos as u32 + 1,
+                )
+            },
+            _ => "".to_string(),
         }
     }
 
@@ -187,10 +288,20 @@ impl ParseGeneric for CpuidResult {
         let phy = addr_size.physical;
         let virt = addr_size.virtual_;
 
-        format!("\
-            [Address size: {phy:2}-bits physical\
-            {LN_PAD}{PAD} {virt:2}-bits virtual]\
-        ")
+        if addr_size.guest_physical != 0 {
+            let guest = addr_size.guest_physical;
+
+            format!("\
+                [Address size: {phy:2}-bits physical\
+                {LN_PAD}{PAD} {virt:2}-bits virtual\
+                {LN_PAD}{PAD} {guest:2}-bits guest physical]\
+            ")
+        } else {
+            format!("\
+                [Address size: {phy:2}-bits physical\
+                {LN_PAD}{PAD} {virt:2}-bits virtual]\
+            ")
+        }
     }
 
     fn ftr_ext_id_80_08h_ebx(&self) -> String {
@@ -208,19 +319,23 @@ impl ParseGeneric for CpuidResult {
             Some(prop) => prop,
             None => return "".to_string(),
         };
-        
-        let inclusive = if cache.inclusive {
-            "[Inclusive]"
-        } else {
-            ""
-        }.to_string();
 
-        format!("[L{}{},{:>3}_way,{:>4}_{}] {inclusive}",
-            cache.level,
-            &cache.cache_type.to_string()[..1],
-            cache.way,
-            cache.size_in_the_unit(),
-            &cache.size_unit.to_string()[..1],
-        )
+        fmt_cache_prop(&cache)
     }
 }
+
+pub(crate) fn fmt_cache_prop(cache: &libcpuid_dump::CacheProp) -> String {
+    let inclusive = if cache.inclusive {
+        "[Inclusive]"
+    } else {
+        ""
+    }.to_string();
+
+    format!("[L{}{},{:>3}_way,{:>4}_{}] {inclusive}",
+        cache.level,
+        &cache.cache_type.to_string()[..1],
+        cache.way,
+        cache.size_in_the_unit(),
+        &cache.size_unit.to_string()[..1],
+    )
+}
@@ -0,0 +1,73 @@
+use super::*;
+use libcpuid_dump::{HypervisorInfo, HypervisorVendor};
+
+pub trait ParseHypervisor {
+    fn hypervisor_40h(&self, hv: &HypervisorInfo, leaf: u32) -> String;
+    fn kvm_40_01h(&self) -> String;
+    fn hyperv_40h(&self, offset: u32) -> String;
+}
+
+impl ParseHypervisor for CpuidResult {
+    fn hypervisor_40h(&self, hv: &HypervisorInfo, leaf: u32) -> String {
+        let offset = leaf - hv.base;
+
+        if offset == 0x0 {
+            return format!("[{}]{LN_PAD}[Max Leaf: {:#010X}]", hv.vendor, hv.max_leaf);
+        }
+
+        match hv.vendor {
+            HypervisorVendor::Kvm if offset == 0x1 => self.kvm_40_01h(),
+            HypervisorVendor::HyperV => self.hyperv_40h(offset),
+            HypervisorVendor::Xen if offset == 0x1 => format!("[Xen {}.{}]", self.eax >> 16, self.eax & 0xFFFF),
+            _ => "".to_string(),
+        }
+    }
+
+    fn kvm_40_01h(&self) -> String {
+        let kvm = libcpuid_dump::KvmInfo::from(self);
+        let mut ftr: Vec<String> = kvm.available_features().map(|ftr| ftr.to_string()).collect();
+
+        if kvm.realtime_hint {
+            ftr.push("RealtimeHint".to_string());
+        }
+
+        align_mold_ftr(&ftr)
+    }
+
+    fn hyperv_40h(&self, offset: u32) -> String {
+        use libcpuid_dump::HyperVEnlightenment;
+
+        match offset {
+            0x1 => {
+                let interface = String::from_utf8_lossy(&self.eax.to_le_bytes()).to_string();
+
+                format!("[Interface: {interface:?}]")
+            },
+            0x2 => format!("[Version: {}.{}.{}]", self.ebx >> 16, self.ebx & 0xFFFF, self.eax),
+            0x3 => {
+                let ftr = [
+                    str_detect_ftr(self.eax, &ftr_hyperv_40_03_eax()),
+                    str_detect_ftr(self.ebx, &ftr_hyperv_40_03_ebx()),
+                    str_detect_ftr(self.edx, &ftr_hyperv_40_03_edx()),
+                ].concat();
+
+                align_mold_ftr(&ftr)
+            },
+            0x4 => {
+                let ftr: Vec<String> = HyperVEnlightenment::ALL
+                    .iter()
+                    .filter(|e| (self.eax >> (**e as u32)) & 0b1 != 0)
+                    .map(|e| e.to_string())
+                    .collect();
+                let spinlock = match self.ebx {
+                    u32::MAX => "Never".to_string(),
+                    retries => retries.to_string(),
+                };
+
+                format!("{}{LN_PAD}[Spinlock Retries: {spinlock}]", align_mold_ftr(&ftr))
+            },
+            0x5 => format!("[Max VP: {}, Max LP: {}]", self.eax, self.ebx),
+            _ => "".to_string(),
+        }
+    }
+}
@@ -2,13 +2,208 @@ use super::*;
 use crate::PARSE_WIDTH;
 
 pub trait ParseIntel {
+    fn cache_desc_intel_00_02h(&self) -> String;
+    fn perfmon_intel_00_0ah(&self) -> String;
+    fn perfmon_ext_intel_00_23h(&self, sub_leaf: u32) -> String;
+    fn sgx_intel_00_12h(&self, sgx: &libcpuid_dump::SgxInfo, sub_leaf: u32) -> String;
+    fn pt_intel_00_14h(&self, sub_leaf: u32) -> String;
+    fn tsc_crystal_intel_00_15h(&self) -> String;
     fn clock_speed_intel_00_16h(&self) -> String;
     fn intel_tlb_param_00_18h(&self) -> String;
+    fn key_locker_intel_00_19h(&self) -> String;
+    fn pconfig_intel_00_1bh(&self) -> String;
     fn intel_hybrid_1ah(&self) -> String;
+    fn amx_tile_intel_00_1dh(&self, sub_leaf: u32) -> String;
+    fn amx_tmul_intel_00_1eh(&self) -> String;
     fn v2_ext_topo_intel_1fh(&self) -> String;
+    fn tdx_intel_00_21h(&self, sub_leaf: u32) -> String;
+    fn avx10_intel_00_24h(&self, sub_leaf: u32) -> String;
 }
 
 impl ParseIntel for CpuidResult {
+    fn cache_desc_intel_00_02h(&self) -> String {
+        use libcpuid_dump::{CacheDesc, CacheType};
+
+        let desc: Vec<String> = CacheDesc::iter_from_cpuid(self).map(|desc| match desc {
+            CacheDesc::Cache(cache) => fmt_cache_prop(&cache),
+            CacheDesc::Tlb(tlb) => {
+                let tlb_type = match tlb.tlb_type {
+                    CacheType::Data => "d",
+                    CacheType::Instruction => "i",
+                    _ => "",
+                };
+                let page: Vec<&str> = [
+                    (tlb.page_4k, "4K"),
+                    (tlb.page_2m, "2M"),
+                    (tlb.page_4m, "4M"),
+                    (tlb.page_1g, "1G"),
+                ].iter().filter_map(|(flag, page)| flag.then_some(*page)).collect();
+
+                format!("[L{}{tlb_type}TLB {}: {}]", tlb.level, page.join("/"), tlb.info)
+            },
+            CacheDesc::TraceCache { k_uops, way } => format!("[Trace {k_uops}K-uop, {way}_way]"),
+            CacheDesc::Prefetch(size) => format!("[Prefetch {size}B]"),
+            CacheDesc::NoL2OrL3 => "[No L2 or L3]".to_string(),
+            CacheDesc::UseLeaf18h => "[TLB: Leaf 0x18]".to_string(),
+            CacheDesc::UseLeaf04h => "[Cache: Leaf 0x4]".to_string(),
+            CacheDesc::Unknown(desc) => format!("[Unknown {desc:#04X}]"),
+        }).collect();
+
+        desc.join(LN_PAD)
+    }
+
+    fn perfmon_intel_00_0ah(&self) -> String {
+        let perfmon = libcpuid_dump::ArchPerfmon::from(self);
+
+        if perfmon.version == 0 {
+            return "".to_string();
+        }
+
+        let events: Vec<String> = perfmon.available_events().map(|event| event.to_string()).collect();
+        let any_thread = if perfmon.any_thread_deprecated {
+            format!("{LN_PAD}[AnyThread deprecated]")
+        } else {
+            "".to_string()
+        };
+
+        format!("\
+            [ArchPerfmon v{version}]\
+            {LN_PAD}[GP Counter: {num_gp}, {gp_width}-bit]\
+            {LN_PAD}[Fixed Counter: {fixed:#06b}, {fixed_width}-bit]\
+            {any_thread}\
+            {LN_PAD}{events}\
+        ",
+            version = perfmon.version,
+            num_gp = perfmon.num_gp_counter,
+            gp_width = perfmon.gp_counter_width,
+            fixed = perfmon.fixed_counter_bitmap(),
+            fixed_width = perfmon.fixed_counter_width,
+            events = align_mold_ftr(&events),
+        )
+    }
+
+    fn perfmon_ext_intel_00_23h(&self, sub_leaf: u32) -> String {
+        use libcpuid_dump::{ArchPerfmonEvent, ArchPerfmonExt};
+
+        let mut ext = ArchPerfmonExt::default();
+        ext.update(sub_leaf, self);
+
+        match sub_leaf {
+            0x0 => {
+                let mut v = vec![format!("[Valid sub-leaf: {:#06b}]", ext.valid_sub_leaf)];
+
+                if ext.umask2 { v.push(format!("{LN_PAD}[UnitMask2]")) }
+                if ext.eq_bit { v.push(format!("{LN_PAD}[EQ-bit]")) }
+
+                v.concat()
+            },
+            0x1 => format!(
+                "[GP Counter: {:#010b}]{LN_PAD}[Fixed Counter: {:#010b}]",
+                ext.gp_counter_bitmap,
+                ext.fixed_counter_bitmap,
+            ),
+            0x2 => format!(
+                "[ACR GP Counter: {:#010b}]{LN_PAD}[ACR Fixed Counter: {:#010b}]",
+                ext.acr_gp_counter_bitmap,
+                ext.acr_fixed_counter_bitmap,
+            ),
+            0x3 => {
+                let events: Vec<String> = ArchPerfmonEvent::ALL
+                    .iter()
+                    .filter(|event| (ext.events >> (**event as u32)) & 0b1 != 0)
+                    .map(|event| event.to_string())
+                    .collect();
+
+                align_mold_ftr(&events)
+            },
+            _ => "".to_string(),
+        }
+    }
+
+    fn sgx_intel_00_12h(&self, sgx: &libcpuid_dump::SgxInfo, sub_leaf: u32) -> String {
+        match sub_leaf {
+            0x0 => {
+                let ftr: Vec<String> = [
+                    (sgx.sgx1, "SGX1"),
+                    (sgx.sgx2, "SGX2"),
+                    (sgx.enclv, "ENCLV"),
+                    (sgx.encls_c, "ENCLS-C"),
+                    (sgx.everifyreport2, "EVERIFYREPORT2"),
+                    (sgx.eupdatesvn, "EUPDATESVN"),
+                    (sgx.edeccssa, "EDECCSSA"),
+                ].iter().filter(|(has, _)| *has).map(|(_, name)| name.to_string()).collect();
+
+                format!("\
+                    {ftr}\
+                    {LN_PAD}[MISCSELECT: {misc:#010X}]\
+                    {LN_PAD}[Max Enclave Size: 2^{not64} (32-bit), 2^{b64} (64-bit)]\
+                ",
+                    ftr = align_mold_ftr(&ftr),
+                    misc = sgx.miscselect,
+                    not64 = sgx.max_enclave_size_not64,
+                    b64 = sgx.max_enclave_size_64,
+                )
+            },
+            0x1 => format!("[ATTRIBUTES: {:#018X}]{LN_PAD}[XFRM: {:#018X}]", sgx.attributes, sgx.xfrm),
+            _ => match libcpuid_dump::SgxEpcSection::option_from_cpuid(self) {
+                Some(epc) => format!(
+                    "[EPC: {:#X}, {} MiB, {}]",
+                    epc.base,
+                    epc.size >> 20,
+                    epc.protection,
+                ),
+                None => "".to_string(),
+            },
+        }
+    }
+
+    fn pt_intel_00_14h(&self, sub_leaf: u32) -> String {
+        match sub_leaf {
+            0x0 => {
+                let ftr = [
+                    str_detect_ftr(self.ebx, &ftr_intel_00_14_ebx_x0()),
+                    str_detect_ftr(self.ecx, &ftr_intel_00_14_ecx_x0()),
+                ].concat();
+
+                align_mold_ftr(&ftr)
+            },
+            0x1 => format!("\
+                [Addr Ranges: {ranges}]\
+                {LN_PAD}[MTC Period: {mtc:#018b}]\
+                {LN_PAD}[CYC Threshold: {cyc:#018b}]\
+                {LN_PAD}[PSB Freq: {psb:#018b}]\
+            ",
+                ranges = self.eax & 0b111,
+                mtc = self.eax >> 16,
+                cyc = self.ebx & 0xFFFF,
+                psb = self.ebx >> 16,
+            ),
+            _ => "".to_string(),
+        }
+    }
+
+    fn tsc_crystal_intel_00_15h(&self) -> String {
+        let [denominator, numerator, crystal_hz] = [self.eax, self.ebx, self.ecx];
+
+        if denominator == 0 || numerator == 0 {
+            return "".to_string();
+        }
+
+        let freq = if crystal_hz != 0 {
+            let tsc_hz = crystal_hz as u64 * numerator as u64 / denominator as u64;
+
+            format!(
+                "{LN_PAD}[Crystal {} MHz, TSC {} MHz]",
+                crystal_hz as f64 / 1_000_000f64,
+                tsc_hz as f64 / 1_000_000f64,
+            )
+        } else {
+            "".to_string()
+        };
+
+        format!("[TSC/Crystal: {numerator}/{denominator}]{freq}")
+    }
+
     fn clock_speed_intel_00_16h(&self) -> String {
         format!(
             "[Base {}, Max {}, Bus {} MHz]",
@@ -57,6 +252,28 @@ impl ParseIntel for CpuidResult {
         )
     }
 
+    fn key_locker_intel_00_19h(&self) -> String {
+        let ftr = [
+            str_detect_ftr(self.eax, &ftr_intel_00_19_eax_x0()),
+            str_detect_ftr(self.ebx, &ftr_intel_00_19_ebx_x0()),
+            str_detect_ftr(self.ecx, &ftr_intel_00_19_ecx_x0()),
+        ].concat();
+
+        align_mold_ftr(&ftr)
+    }
+
+    fn pconfig_intel_00_1bh(&self) -> String {
+        let Some(targets) = libcpuid_dump::PconfigTarget::option_from_cpuid(self) else {
+            return "".to_string();
+        };
+        let targets: Vec<String> = IntoIterator::into_iter(targets)
+            .flatten()
+            .map(|target| format!("[PCONFIG Target: {target}]"))
+            .collect();
+
+        targets.join(LN_PAD)
+    }
+
     fn intel_hybrid_1ah(&self) -> String {
         use libcpuid_dump::HybridInfo;
 
@@ -70,9 +287,84 @@ impl ParseIntel for CpuidResult {
         format!("[Type: {core_type}, Model: {native_model_id}]")
     }
 
+    fn amx_tile_intel_00_1dh(&self, sub_leaf: u32) -> String {
+        if sub_leaf == 0x0 {
+            return format!("[Max Palette: {}]", self.eax);
+        }
+
+        let palette = libcpuid_dump::AmxPalette::from_cpuid(sub_leaf, self);
+
+        format!("\
+            [Palette {id}: {total} bytes]\
+            {LN_PAD}[Tile: {names} x {tile} bytes]\
+            {LN_PAD}[Row: {rows} x {row} bytes]\
+        ",
+            id = palette.palette_id,
+            total = palette.total_tile_bytes,
+            names = palette.max_names,
+            tile = palette.bytes_per_tile,
+            rows = palette.max_rows,
+            row = palette.bytes_per_row,
+        )
+    }
+
+    fn amx_tmul_intel_00_1eh(&self) -> String {
+        let tmul = libcpuid_dump::AmxTmul::from(self);
+
+        format!("[TMUL MaxK: {}, MaxN: {} bytes]", tmul.tmul_maxk, tmul.tmul_maxn)
+    }
+
     fn v2_ext_topo_intel_1fh(&self) -> String {
         let topo = libcpuid_dump::IntelExtTopo::from(self);
 
         format!("[LevelType: {}, num: {}]", topo.level_type, topo.num_proc)
     }
+
+    fn tdx_intel_00_21h(&self, sub_leaf: u32) -> String {
+        if sub_leaf == 0x0 && libcpuid_dump::CcGuest::is_tdx_signature(self) {
+            "[TDX Guest]".to_string()
+        } else {
+            "".to_string()
+        }
+    }
+
+    fn avx10_intel_00_24h(&self, sub_leaf: u32) -> String {
+        if sub_leaf != 0x0 {
+            return "".to_string();
+        }
+
+        let avx10 = libcpuid_dump::Avx10Info::from(self);
+        let vl: Vec<String> = [(avx10.vl128, "VL128"), (avx10.vl256, "VL256"), (avx10.vl512, "VL512")]
+            .iter()
+            .filter(|(has, _)| *has)
+            .map(|(_, vl)| vl.to_string())
+            .collect();
+
+        format!("[AVX10.{}]{LN_PAD}{}", avx10.version, align_mold_ftr(&vl))
+    }
+}
+
+#[test]
+fn test_sgx_intel_00_12h() {
+    use crate::{args::DumpFormat, load_raw_dump::parse_raw_dump, SnapshotDisp};
+
+    /* SGX1, SGX2 and a 64 GiB EPC section, "-raw" output */
+    let dump = |leaf_07h_ebx: &str| format!("\
+        0x00000000 0x0:  0x0000001B 0x756E6547 0x6C65746E 0x49656E69\n\
+        0x00000007 0x0:  0x00000000 {leaf_07h_ebx} 0x00000000 0x00000000\n\
+        0x00000012 0x0:  0x00000003 0x00000000 0x00000000 0x0000381F\n\
+        0x00000012 0x1:  0x000000B6 0x00000000 0x000002E7 0x00000000\n\
+        0x00000012 0x2:  0x00000001 0x00000080 0x00000001 0x00000010\n\
+    ");
+    let parse = |dump: &str| parse_raw_dump(dump)[0].select_pool(DumpFormat::Parse);
+
+    /* SGX: EBX[2] */
+    let sgx = parse(&dump("0x00000004"));
+
+    assert!(sgx.contains("SGX1") && sgx.contains("SGX2") && sgx.contains("2^56 (64-bit)"));
+    assert!(sgx.contains("[XFRM: 0x00000000000002E7]") && sgx.contains("[EPC: 0x8000000000, 65536 MiB, "));
+
+    let no_sgx = parse(&dump("0x00000000"));
+
+    assert!(!no_sgx.contains("SGX1") && !no_sgx.contains("EPC"));
 }
@@ -0,0 +1,17 @@
+use super::*;
+
+pub trait ParseZhaoxin {
+    fn max_leaf_zhaoxin_c0_00h(&self) -> String;
+    fn padlock_zhaoxin_c0_01h(&self) -> String;
+}
+
+impl ParseZhaoxin for CpuidResult {
+    fn max_leaf_zhaoxin_c0_00h(&self) -> String {
+        format!("[Max Leaf: {:#010X}]", self.eax)
+    }
+
+    /* PadLock units, "_EN": enabled by the MSR */
+    fn padlock_zhaoxin_c0_01h(&self) -> String {
+        align_mold_ftr(&str_detect_ftr(self.edx, &ftr_centaur_c0_01_edx_x0()))
+    }
+}
@@ -1,26 +1,23 @@
 use crate::{CpuidResult, CpuVendor};
+use libcpuid_dump::RawCpuid;
 use super::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct RawCpuid {
-    pub leaf: u32,
-    pub sub_leaf: u32,
-    pub result: CpuidResult,
+/* Keep the columns aligned for Sub-leaf >= 0x10 */
+fn sub_leaf_pad(sub_leaf: u32) -> &'static str {
+    if sub_leaf > 0xF { " " } else { "  " }
 }
 
-impl RawCpuid {
-    pub fn exe(leaf: u32, sub_leaf: u32) -> Self {
-        Self {
-            leaf,
-            sub_leaf,
-            result: cpuid!(leaf, sub_leaf),
-        }
-    }
-
-    pub fn check_result_zero(&self) -> bool {
-        self.result == CpuidResult { eax: 0x0, ebx: 0x0, ecx: 0x0, edx: 0x0 }
-    }
+pub trait RawCpuidFmt {
+    fn parse(&self, vendor: &CpuVendor) -> String;
+    fn result(&self, end_str: &str) -> String;
+    fn raw_fmt(&self, _: &CpuVendor) -> String;
+    fn parse_fmt(&self, vendor: &CpuVendor) -> String;
+    fn bin_fmt(&self, _: &CpuVendor) -> String;
+    fn compat_fmt(&self, _: &CpuVendor) -> String;
+    fn debug_fmt(&self, _: &CpuVendor) -> String;
+}
 
+impl RawCpuidFmt for RawCpuid {
     fn parse(&self, vendor: &CpuVendor) -> String {
         let cpuid = self.result;
 
@@ -41,6 +38,8 @@ impl RawCpuid {
             },
             0xB => cpuid.topo_ext_00_0bh(),
             0xD => cpuid.xstate_00_0dh(self.sub_leaf),
+            0xF => cpuid.rdt_monitor_00_0fh(self.sub_leaf),
+            0x10 => cpuid.rdt_alloc_00_10h(self.sub_leaf),
             0x8000_0001 => [
                 if let CpuVendor::AuthenticAMD = vendor {
                     [cpuid.pkgtype_amd_80_01h(), lnpad!()].concat()
@@ -77,18 +76,31 @@ impl RawCpuid {
                     0x8000_001E => cpuid.cpu_topo_amd_80_1eh(),
                     0x8000_001F => [
                         cpuid.encrypt_ftr_amd_80_1fh(),
-                        cpuid.reduction_phys_addr_amd_80_1fh(),
+                        cpuid.mem_encrypt_amd_80_1fh(),
                     ].concat(),
+                    0x8000_0020 => cpuid.pqe_amd_80_20h(self.sub_leaf),
                     0x8000_0021 => cpuid.ext_amd_80_21h(),
+                    0x8000_0022 => cpuid.perfmon_amd_80_22h(),
                     0x8000_0026 => cpuid.amd_ext_topo_80_26h(),
                     _ => "".to_string(),
                 },
                 CpuVendor::GenuineIntel => match self.leaf {
+                    0x2 => cpuid.cache_desc_intel_00_02h(),
                     0x4 => cpuid.cache_prop(),
+                    0xA => cpuid.perfmon_intel_00_0ah(),
+                    0x14 => cpuid.pt_intel_00_14h(self.sub_leaf),
+                    0x15 => cpuid.tsc_crystal_intel_00_15h(),
                     0x16 => cpuid.clock_speed_intel_00_16h(),
                     0x18 => cpuid.intel_tlb_param_00_18h(),
+                    0x19 => cpuid.key_locker_intel_00_19h(),
                     0x1A => cpuid.intel_hybrid_1ah(),
+                    0x1B => cpuid.pconfig_intel_00_1bh(),
+                    0x1D => cpuid.amx_tile_intel_00_1dh(self.sub_leaf),
+                    0x1E => cpuid.amx_tmul_intel_00_1eh(),
                     0x1F => cpuid.v2_ext_topo_intel_1fh(),
+                    0x21 => cpuid.tdx_intel_00_21h(self.sub_leaf),
+                    0x23 => cpuid.perfmon_ext_intel_00_23h(self.sub_leaf),
+                    0x24 => cpuid.avx10_intel_00_24h(self.sub_leaf),
                     _ => "".to_string(),
                 },
                 CpuVendor::CentaurHauls |
@@ -96,6 +108,8 @@ impl RawCpuid {
                     0x4 => cpuid.cache_prop(),
                     0x8000_0005 => cpuid.l1_amd_80_05h(),
                     0x8000_0006 => cpuid.l2_amd_80_06h(),
+                    0xC000_0000 => cpuid.max_leaf_zhaoxin_c0_00h(),
+                    0xC000_0001 => cpuid.padlock_zhaoxin_c0_01h(),
                     _ => "".to_string(),
                 },
                 _ => "".to_string(),
@@ -105,9 +119,10 @@ impl RawCpuid {
 
     fn result(&self, end_str: &str) -> String {
         format!(
-            "  {:#010X} {:#3X}:  {:#010X} {:#010X} {:#010X} {:#010X}  {}\n",
+            "  {:#010X} {:#3X}:{}{:#010X} {:#010X} {:#010X} {:#010X}  {}\n",
             self.leaf,
             self.sub_leaf,
+            sub_leaf_pad(self.sub_leaf),
             self.result.eax,
             self.result.ebx,
             self.result.ecx,
@@ -116,15 +131,15 @@ impl RawCpuid {
         )
     }
 
-    pub fn raw_fmt(&self, _: &CpuVendor) -> String {
+    fn raw_fmt(&self, _: &CpuVendor) -> String {
         self.result("")
     }
 
-    pub fn parse_fmt(&self, vendor: &CpuVendor) -> String {
+    fn parse_fmt(&self, vendor: &CpuVendor) -> String {
         self.result(&self.parse(vendor))
     }
 
-    pub fn bin_fmt(&self, _: &CpuVendor) -> String {
+    fn bin_fmt(&self, _: &CpuVendor) -> String {
         let separate = |reg: u32| -> String {
             let tmp = format!("{reg:032b}");
 
@@ -147,17 +162,19 @@ impl RawCpuid {
 
         const PAD: &str = unsafe { std::str::from_utf8_unchecked(&[b' '; 18]) };
 
-        format!("  {leaf:#010X} {sub_leaf:#03X}:  {eax}  {ebx} \n{PAD} {ecx}  {edx} \n")
+        let sub_leaf_pad = sub_leaf_pad(self.sub_leaf);
+
+        format!("  {leaf:#010X} {sub_leaf:#03X}:{sub_leaf_pad}{eax}  {ebx} \n{PAD} {ecx}  {edx} \n")
     }
 
-    pub fn compat_fmt(&self, _: &CpuVendor) -> String {
+    fn compat_fmt(&self, _: &CpuVendor) -> String {
         let [leaf, sub_leaf] = [self.leaf, self.sub_leaf];
         let CpuidResult { eax, ebx, ecx, edx } = self.result;
 
         format!("   {leaf:#010x} {sub_leaf:#04x}: eax={eax:#010x} ebx={ebx:#010x} ecx={ecx:#010x} edx={edx:#010x}\n")
     }
 
-    pub fn debug_fmt(&self, _: &CpuVendor) -> String {
+    fn debug_fmt(&self, _: &CpuVendor) -> String {
         format!("{:#X?}\n", self)
     }
 }